@@ -9,7 +9,7 @@ fn main() {
 
     println!("Foo:\n{}", foo);
 
-    let all = dict.query("*").regex().execute().unwrap().into_grouped();
+    let all = dict.query("*").wildcard().execute().unwrap().into_grouped();
 
     println!("All:\n{}", all);
 }
\ No newline at end of file
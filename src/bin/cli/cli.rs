@@ -1,18 +1,35 @@
 extern crate colored;
+extern crate serde_json;
 
 use config::Config;
-use dictcc::{Dict, Language, QueryType};
+use chrono::{Datelike, Local};
+use dictcc::{Dict, DictEntry, DictQuery, EntrySide, Gender, Language, QueryField, QueryType, WordClass};
+use dictcc::cache::SharedDict;
+use dictcc::filter::{EntryFilter, WordClassFilter};
+use dictcc::graph::RelatedTerm;
+use dictcc::region::Region;
+use dictcc::subject::Subject;
+use dictcc::suggest::Suggestion;
 use error::DictCliResult;
 #[cfg(unix)]
 use pager::Pager;
 use simplelog::{self, LevelFilter, TermLogger};
 use std::default::Default;
+use std::fs::File;
 use std::io;
+use std::io::BufReader;
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 use error::DictCliError;
 
+/// Maximal number of suggestions shown, if a query found no translations.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Maximal number of cached query results.
+const QUERY_CACHE_SIZE: usize = 100;
+
 #[derive(StructOpt, Debug, Clone)]
 #[structopt(name = "dictcc", about = "Offline Translator powered by the database of dict.cc")]
 pub struct Cli {
@@ -48,11 +65,114 @@ pub struct Cli {
     /// "w" | "word" - Matches on a word in an entry.
     /// "e" | "exact" - Must match the complete entry.
     /// "r" | "regex" - Matches using the regex provided by the user.
+    /// "g" | "glob" - Matches using a glob pattern with `*`, `?` and `[abc]`, e.g. `Haus*`.
+    /// "b" | "bool" - Boolean query language, e.g. `haus -tür class:noun gender:f lang:de`.
+    /// "p" | "phonetic" - Matches words, which sound like the query, e.g. "filosofie" finds "Philosophie".
     #[structopt(short = "t", long = "type", default_value = "Word")]
     pub query_type: QueryType,
 
+    /// "w" | "word" - Searches the words with their optional parts.
+    /// "a" | "acronym" - Searches the acronyms, e.g. "USA" finds "Vereinigte Staaten <USA>".
+    /// "c" | "comment" - Searches the comments, e.g. "[Kommentar]".
+    /// "all" - Searches the words, acronyms and comments.
+    #[structopt(short = "f", long = "field", default_value = "word")]
+    pub field: QueryField,
+
+    /// Distinguish between upper and lower case, e.g. "Essen" and "essen".
+    #[structopt(short = "s", long = "case-sensitive")]
+    pub case_sensitive: bool,
+
+    /// Only show entries of this word class (e.g. "noun", "verb", "adj").
+    /// Can be given multiple times, entries matching any of the classes are shown.
+    #[structopt(long = "class")]
+    pub word_classes: Vec<WordClass>,
+
+    /// Only show entries of this subject (e.g. "med", "tech", "law", "comp", "bot").
+    /// Can be given multiple times, entries matching any of the subjects are shown.
+    #[structopt(long = "subject")]
+    pub subjects: Vec<Subject>,
+
+    /// Only show entries, which are used in this region (e.g. "br", "am", "at", "ch").
+    /// Entries of other regions of the same language, e.g. "[Am.]" for "br", are hidden.
+    #[structopt(long = "region")]
+    pub region: Option<Region>,
+
+    /// Hide vulgar and pejorative entries, e.g. "[vulg.]" or "[pej.]".
+    #[structopt(long = "family-safe")]
+    pub family_safe: bool,
+
+    /// Only show entries with this gender tag ("f", "m", "n", "pl", "sg").
+    #[structopt(long = "gender")]
+    pub gender: Option<Gender>,
+
+    /// Only show entries with at most this number of words on each side.
+    #[structopt(long = "max-words")]
+    pub max_words: Option<u8>,
+
+    /// Only show entries with any gender tag, e.g. nouns.
+    /// In contrast to "--gender", the gender itself is not restricted.
+    #[structopt(long = "with-gender")]
+    pub with_gender: bool,
+
+    /// Show the romanized form of Cyrillic and Greek words next to the words.
+    #[structopt(long = "romanize")]
+    pub romanize: bool,
+
+    /// Show German nouns with their definite articles, e.g. "die Wohnung {f}".
+    #[structopt(long = "articles")]
+    pub articles: bool,
+
+    /// Show placeholders expanded, e.g. "jemandem helfen" instead of "jdm. helfen".
+    #[structopt(long = "expand-placeholders")]
+    pub expand_placeholders: bool,
+
+    /// Do not match Latin-script queries against the romanized form of Cyrillic and Greek words.
+    #[structopt(long = "no-transliterate")]
+    pub no_transliterate: bool,
+
+    /// Translate the terms in this file (one per line) and print the translations as tab separated values.
+    /// The query type and the filter options are applied to every term.
+    #[structopt(long = "batch", parse(from_os_str))]
+    pub batch_path: Option<PathBuf>,
+
+    /// Show the terms related to this term through translations as a tree,
+    /// e.g. the translations of the term and their translations.
+    #[structopt(long = "related")]
+    pub related: Option<String>,
+
+    /// The maximal number of translations between the term of "--related" and a related term.
+    #[structopt(short = "k", long = "depth", default_value = "2")]
+    pub depth: usize,
+
+    /// Show random entries, e.g. for vocabulary training. The filter options are applied.
+    #[structopt(long = "random")]
+    pub random: bool,
+
+    /// The number of entries shown by "--random".
+    #[structopt(short = "n", long = "count", default_value = "10")]
+    pub count: usize,
+
+    /// The seed of "--random". The same seed always shows the same entries.
+    #[structopt(long = "seed")]
+    pub seed: Option<u64>,
+
+    /// Show the word of the day. The filter options are applied.
+    #[structopt(long = "wotd")]
+    pub word_of_the_day: bool,
+
+    /// Show statistics about the content of the database.
+    #[structopt(long = "stats")]
+    pub stats: bool,
+
+    /// Print the statistics of "--stats" as JSON.
+    #[structopt(long = "json")]
+    pub json: bool,
+
+    /// The number of most frequent words shown by "--stats" for each language.
+    #[structopt(long = "top", default_value = "10")]
+    pub top: usize,
+
     /// The query to be translated.
-    #[structopt(required_unless = "interactive_mode")]
     pub query: Option<String>,
 }
 
@@ -62,6 +182,12 @@ pub fn run_cli(cli: Cli) -> DictCliResult<()> {
 
     debug!("cli = {:?}", cli);
 
+    let has_mode = cli.related.is_some() || cli.random || cli.word_of_the_day || cli.stats;
+
+    if cli.query.is_none() && !has_mode && cli.batch_path.is_none() && !cli.interactive_mode {
+        return Err(DictCliError::NoQuery);
+    }
+
     if cli.no_color {
         colored::control::set_override(false)
     }
@@ -78,6 +204,29 @@ pub fn run_cli(cli: Cli) -> DictCliResult<()> {
         Dict::create(config.get_database_path())?
     };
 
+    if let Some(ref term) = cli.related {
+        return run_related(&cli, &dict, term, cli.depth);
+    }
+
+    if cli.random {
+        return run_random(&cli, &dict, cli.count, cli.seed);
+    }
+
+    if cli.word_of_the_day {
+        return run_word_of_the_day(&cli, &dict);
+    }
+
+    if cli.stats {
+        return run_stats(&dict, cli.json, cli.top);
+    }
+
+    if let Some(ref batch_path) = cli.batch_path {
+        return run_batch(&cli, batch_path, &dict);
+    }
+
+    // Repeated queries of the interactive mode are answered by the cache
+    let dict = SharedDict::with_cache(dict, QUERY_CACHE_SIZE);
+
     let mut cli = cli;
 
     if cli.query.is_some() {
@@ -108,13 +257,13 @@ fn init_log(cli: &Cli) -> DictCliResult<()> {
     Ok(())
 }
 
-fn update_cli_interactive(cli: &mut Cli) -> DictCliResult<bool> {
-    fn read_stdin_line() -> DictCliResult<String> {
-        let mut line = String::new();
-        ::std::io::stdin().read_line(&mut line)?;
-        Ok(line.trim_right_matches(|c| c == '\n' || c == '\r').to_string())
-    }
+fn read_stdin_line() -> DictCliResult<String> {
+    let mut line = String::new();
+    ::std::io::stdin().read_line(&mut line)?;
+    Ok(line.trim_right_matches(|c| c == '\n' || c == '\r').to_string())
+}
 
+fn update_cli_interactive(cli: &mut Cli) -> DictCliResult<bool> {
     println!("Enter query language (if empty, the query is bidirectional):");
     let tmp_lang = read_stdin_line()?;
     cli.language = if tmp_lang == "" {
@@ -123,7 +272,7 @@ fn update_cli_interactive(cli: &mut Cli) -> DictCliResult<bool> {
         Some(Language::from_str(&tmp_lang)?)
     };
 
-    println!("Enter query type (\"w(ord)\" [default], \"e(xact)\", \"r(egex)\"):");
+    println!("Enter query type (\"w(ord)\" [default], \"e(xact)\", \"r(egex)\", \"g(lob)\", \"b(ool)\", \"p(honetic)\"):");
     let tmp_type = read_stdin_line()?;
     cli.query_type = if tmp_type == "" {
         QueryType::Word
@@ -141,21 +290,38 @@ fn update_cli_interactive(cli: &mut Cli) -> DictCliResult<bool> {
 }
 
 
-fn run_query(cli: &Cli, dict: &Dict) -> DictCliResult<()> {
+fn run_query(cli: &Cli, dict: &SharedDict) -> DictCliResult<()> {
     let mut query = dict.query(cli.query.as_ref().unwrap());
+    configure_query(cli, &mut query)?;
 
-    if let Some(ref language) = cli.language {
-        query.source_language(language)?;
-    }
-
-    query.set_type(cli.query_type);
-
-    let query_result = query.execute()?;
+    let query_result = dict.execute(&query)?.sorted_by_relevance();
 
     if query_result.entries().is_empty() {
         println!("Sorry, no translations found!");
+
+        let suggestions = query.suggestions(MAX_SUGGESTIONS);
+
+        if !suggestions.is_empty() {
+            println!("Did you mean:");
+
+            for (index, suggestion) in suggestions.iter().enumerate() {
+                println!("{:>3}: {} ({})", index + 1, suggestion.word, suggestion.language);
+            }
+
+            if cli.interactive_mode {
+                if let Some(suggestion) = choose_suggestion(&suggestions)? {
+                    let mut cli = cli.clone();
+                    cli.query = Some(suggestion.word.clone());
+
+                    return run_query(&cli, dict);
+                }
+            }
+        }
     } else {
-        let query_result_grouped = query_result.into_grouped();
+        let mut query_result_grouped = query_result.into_grouped();
+        query_result_grouped.set_show_romanized(cli.romanize);
+        query_result_grouped.set_show_articles(cli.articles);
+        query_result_grouped.set_expand_placeholders(cli.expand_placeholders);
 
         let mut stdout = io::stdout();
 
@@ -167,4 +333,213 @@ fn run_query(cli: &Cli, dict: &Dict) -> DictCliResult<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+fn run_batch(cli: &Cli, batch_path: &Path, dict: &Dict) -> DictCliResult<()> {
+    let file = File::open(batch_path)?;
+
+    let mut terms = vec![];
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let term = line.trim();
+
+        if !term.is_empty() {
+            terms.push(term.to_string());
+        }
+    }
+
+    let mut query = dict.query("");
+    configure_query(cli, &mut query)?;
+
+    let results = query.execute_batch(&terms)?;
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    writeln!(&mut stdout, "term\t{}\t{}\tword classes", dict.get_left_language(), dict.get_right_language())?;
+
+    for (term, result) in terms.iter().zip(results) {
+        let result = result.sorted_by_relevance();
+
+        // Terms without translations are kept, so that every term of the file is listed
+        if result.entries().is_empty() {
+            writeln!(&mut stdout, "{}\t\t\t", term)?;
+        }
+
+        for entry in result.entries() {
+            writeln!(&mut stdout, "{}\t{}\t{}\t{}", term, entry.left_word, entry.right_word, format_word_classes(entry))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the query options to `query`, except the query term.
+fn configure_query(cli: &Cli, query: &mut DictQuery) -> DictCliResult<()> {
+    if let Some(ref language) = cli.language {
+        query.source_language(language)?;
+    }
+
+    query.set_type(cli.query_type);
+    query.set_field(cli.field);
+    query.case_sensitive(cli.case_sensitive);
+    query.transliterate(!cli.no_transliterate);
+    query.set_filter(entry_filter(cli));
+
+    Ok(())
+}
+
+/// The filter of the entries, as specified by the filter options.
+fn entry_filter(cli: &Cli) -> EntryFilter {
+    let mut filter = EntryFilter::default();
+
+    if !cli.word_classes.is_empty() {
+        filter.word_classes = Some(WordClassFilter::Any(cli.word_classes.clone()));
+    }
+
+    filter.gender = cli.gender;
+    filter.any_gender = cli.with_gender;
+    filter.subjects = cli.subjects.clone();
+    filter.region = cli.region;
+    filter.family_safe = cli.family_safe;
+
+    if let Some(max_words) = cli.max_words {
+        filter.set_max_word_count(EntrySide::Both, max_words);
+    }
+
+    filter
+}
+
+fn run_stats(dict: &Dict, json: bool, top: usize) -> DictCliResult<()> {
+    let stats = dict.stats(top);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        println!("{}", stats);
+    }
+
+    Ok(())
+}
+
+fn run_random(cli: &Cli, dict: &Dict, count: usize, seed: Option<u64>) -> DictCliResult<()> {
+    let seed = match seed {
+        Some(seed) => seed,
+        None => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            now.as_secs() ^ u64::from(now.subsec_nanos())
+        }
+    };
+
+    let entries = dict.random_entries(count, seed, &entry_filter(cli));
+
+    if entries.is_empty() {
+        println!("Sorry, no entries found!");
+    }
+
+    for entry in entries {
+        println!("{}", format_entry(entry));
+    }
+
+    Ok(())
+}
+
+fn run_word_of_the_day(cli: &Cli, dict: &Dict) -> DictCliResult<()> {
+    let today = Local::now().date_naive();
+
+    match dict.word_of_the_day(today.num_days_from_ce() as u64, &entry_filter(cli)) {
+        Some(entry) => {
+            println!("Word of the day ({}):", today);
+            println!("{}", format_entry(entry));
+        }
+        None => println!("Sorry, no entries found!"),
+    }
+
+    Ok(())
+}
+
+fn format_entry(entry: &DictEntry) -> String {
+    format!("{} | {} | {}", entry.left_word, entry.right_word, format_word_classes(entry))
+}
+
+fn format_word_classes(entry: &DictEntry) -> String {
+    entry.word_classes.iter()
+        .map(|word_class| word_class.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn run_related(cli: &Cli, dict: &Dict, term: &str, depth: usize) -> DictCliResult<()> {
+    let graph = dict.graph();
+    let starts = graph.find(term, cli.language.as_ref());
+
+    if starts.is_empty() {
+        println!("Sorry, no translations found!");
+        return Ok(());
+    }
+
+    let mut stdout = io::stdout();
+
+    if !cli.no_pager {
+        #[cfg(unix)] Pager::with_pager("less -r").setup();
+    }
+
+    for start in starts {
+        writeln!(&mut stdout, "{}", format_related_tree(&graph.related(start, depth)))?;
+    }
+
+    Ok(())
+}
+
+/// Formats the related terms as a tree, like the output of `tree`.
+fn format_related_tree(related: &[RelatedTerm]) -> String {
+    fn format_subtree(related: &[RelatedTerm], children: &[Vec<usize>], index: usize, prefix: &str, lines: &mut Vec<String>) {
+        let child_count = children[index].len();
+
+        for (i, &child) in children[index].iter().enumerate() {
+            let is_last = i + 1 == child_count;
+            let term = &related[child].node;
+
+            lines.push(format!("{}{}{} ({})", prefix, if is_last { "└── " } else { "├── " }, term.word, term.language));
+
+            let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
+            format_subtree(related, children, child, &child_prefix, lines);
+        }
+    }
+
+    let root = match related.first() {
+        Some(root) => root,
+        None => return String::new(),
+    };
+
+    let mut children = vec![vec![]; related.len()];
+
+    for (index, term) in related.iter().enumerate() {
+        if let Some(parent) = term.parent {
+            children[parent].push(index);
+        }
+    }
+
+    let mut lines = vec![format!("{} ({})", root.node.word, root.node.language)];
+    format_subtree(related, &children, 0, "", &mut lines);
+
+    lines.join("\n")
+}
+
+fn choose_suggestion(suggestions: &[Suggestion]) -> DictCliResult<Option<&Suggestion>> {
+    println!("Enter the number of a suggestion to search for it (if empty, no suggestion is used):");
+    let choice = read_stdin_line()?;
+
+    if choice.is_empty() {
+        return Ok(None);
+    }
+
+    match choice.trim().parse::<usize>() {
+        Ok(number) if number >= 1 && number <= suggestions.len() => Ok(Some(&suggestions[number - 1])),
+        _ => {
+            println!("Invalid suggestion number: {}", choice);
+            Ok(None)
+        }
+    }
+}
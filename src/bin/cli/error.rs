@@ -3,6 +3,7 @@ extern crate app_dirs;
 extern crate dictcc;
 extern crate toml;
 extern crate simplelog;
+extern crate serde_json;
 
 use dictcc::error::DictError;
 use std::io;
@@ -14,6 +15,9 @@ pub enum DictCliError {
     #[fail(display = "No database path was specified as an option or in previous usage.")]
     NoDatabasePath,
 
+    #[fail(display = "No query was specified. Use the interactive mode, a batch file, \"--related\", \"--random\", \"--wotd\" or \"--stats\" to run without a query.")]
+    NoQuery,
+
     #[fail(display = "{}", _0)]
     DictError(#[cause] DictError),
 
@@ -31,6 +35,9 @@ pub enum DictCliError {
 
     #[fail(display = "{}", _0)]
     TomlSe(#[cause] toml::ser::Error),
+
+    #[fail(display = "{}", _0)]
+    Json(#[cause] serde_json::Error),
 }
 
 impl From<app_dirs::AppDirsError> for DictCliError {
@@ -68,3 +75,9 @@ impl From<simplelog::TermLogError> for DictCliError {
         DictCliError::TermLogError(err)
     }
 }
+
+impl From<serde_json::Error> for DictCliError {
+    fn from(err: serde_json::Error) -> Self {
+        DictCliError::Json(err)
+    }
+}
@@ -9,7 +9,9 @@ extern crate failure;
 #[macro_use]
 extern crate serde_derive;
 extern crate colored;
+extern crate chrono;
 extern crate simplelog;
+extern crate serde_json;
 #[macro_use]
 extern crate log;
 #[cfg(unix)]
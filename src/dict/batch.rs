@@ -0,0 +1,201 @@
+//! Queries of many terms in a single pass over the entries.
+
+use super::*;
+
+use dict::matcher::split_article;
+use std::collections::HashMap;
+
+impl Dict {
+    /// Executes a word query (see `QueryType::Word`) for every term in a single pass over the entries.
+    ///
+    /// Convenience function for `DictQuery::execute_batch`, e.g. to set the direction use
+    /// `dict.query("").set_direction(QueryDirection::ToLeft).execute_batch(terms)`
+    pub fn query_batch<S: AsRef<str>>(&self, terms: &[S]) -> DictResult<Vec<DictQueryResult<&DictEntry>>> {
+        self.query("").execute_batch(terms)
+    }
+}
+
+impl<'a, 'b> DictQuery<'a, 'b> {
+    /// Executes the query for every term in a single pass over the entries.
+    ///
+    /// The query term of the builder is ignored, all other settings (type, filter, paging, ...) are applied to each term.
+    /// Returns a `DictQueryResult` for every term, in the same order as `terms`,
+    /// which is equal to the result of `execute` with the term.
+    ///
+    /// For word and exact queries of the words, the terms are indexed by their first word,
+    /// so only the terms, whose first word occurs in an entry, have to be matched against the entry.
+    /// The romanized words (see `DictQuery::transliterate`) and the placeholders are looked up in the index as well.
+    /// Terms with a German article, e.g. `die Bank`, are also indexed by the first word after the article.
+    /// The terms of other queries are matched against every entry.
+    pub fn execute_batch<S: AsRef<str>>(&self, terms: &[S]) -> DictResult<Vec<DictQueryResult<&'a DictEntry>>> {
+        let matchers = terms.iter()
+            .map(|term| QueryMatcher::new(&self.with_term(term.as_ref())))
+            .collect::<DictResult<Vec<_>>>()?;
+
+        let indexed = match self.query_type {
+            QueryType::Word | QueryType::Exact => self.query_field == QueryField::IndexedWord,
+            QueryType::Regex | QueryType::Wildcard | QueryType::Boolean | QueryType::Phonetic => false,
+        };
+
+        let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+        // Terms without a first word, which have to be matched against every entry
+        let mut unindexed = vec![];
+
+        for (term_index, term) in terms.iter().enumerate() {
+            let term = term.as_ref().to_lowercase();
+
+            let mut first_words = split_words(&term).into_iter().take(1).collect::<Vec<_>>();
+
+            if let Some((_, noun)) = split_article(&term) {
+                first_words.extend(split_words(noun).into_iter().take(1));
+            }
+
+            if !indexed || first_words.is_empty() {
+                unindexed.push(term_index);
+                continue;
+            }
+
+            for first_word in first_words {
+                index.entry(first_word.to_string()).or_default().push(term_index);
+
+                // The romanized words are indexed without diacritics
+                let folded_first_word = normalize::fold_diacritics(first_word);
+
+                if folded_first_word != first_word {
+                    index.entry(folded_first_word).or_default().push(term_index);
+                }
+            }
+        }
+
+        let mut query_matches: Vec<QueryMatches> = matchers.iter()
+            .map(|matcher| QueryMatches {
+                entries: vec![],
+                matches: vec![],
+                relevance_term: matcher.relevance_term.clone(),
+                query_direction: matcher.query_direction,
+            })
+            .collect();
+
+        for (entry_index, entry) in self.dict.entries.iter().enumerate() {
+            let words = match self.query_direction {
+                QueryDirection::ToRight => vec![&entry.left_word],
+                QueryDirection::ToLeft => vec![&entry.right_word],
+                QueryDirection::Bidirectional => vec![&entry.left_word, &entry.right_word],
+            };
+
+            let mut candidates = unindexed.clone();
+
+            for word in words {
+                let indexed_romanized = word.indexed_romanized.as_deref().unwrap_or("");
+                let indexed_full_word = word.indexed_full_word.as_deref().unwrap_or("");
+
+                let indexed_words = split_words(&word.indexed_word).into_iter()
+                    .chain(split_words(indexed_romanized))
+                    .chain(split_words(indexed_full_word));
+
+                for indexed_word in indexed_words {
+                    if let Some(term_indices) = index.get(indexed_word) {
+                        candidates.extend(term_indices);
+                    }
+                }
+            }
+
+            candidates.sort();
+            candidates.dedup();
+
+            for term_index in candidates {
+                let matcher = &matchers[term_index];
+
+                if let Some(matched_side) = matcher.matched_side(entry) {
+                    let term_matches = &mut query_matches[term_index];
+
+                    term_matches.matches.push(matcher.entry_match(entry, matched_side));
+                    term_matches.entries.push(entry_index);
+                }
+            }
+        }
+
+        Ok(query_matches.iter().map(|term_matches| self.rank(term_matches)).collect())
+    }
+
+    /// A copy of the query with another query term.
+    fn with_term<'c>(&self, query_term: &'c str) -> DictQuery<'a, 'c> {
+        DictQuery {
+            dict: self.dict,
+            query_term,
+            query_type: self.query_type,
+            query_direction: self.query_direction,
+            query_field: self.query_field,
+            case_sensitive: self.case_sensitive,
+            match_mode: self.match_mode,
+            transliterate: self.transliterate,
+            relevance_model: self.relevance_model.clone(),
+            filter: self.filter.clone(),
+            offset: self.offset,
+            limit: self.limit,
+        }
+    }
+}
+
+/// Splits at the word delimiters of a word query.
+fn split_words(s: &str) -> Vec<&str> {
+    s.split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_batch_equals_queries(query: &DictQuery, terms: &[&str]) {
+        let results = query.execute_batch(terms).unwrap();
+        assert_eq!(terms.len(), results.len());
+
+        for (term, result) in terms.iter().zip(results) {
+            let expected = query.with_term(term).execute().unwrap();
+            assert_eq!(expected, result, "batch result of {:?}", term);
+        }
+    }
+
+    #[test]
+    fn test_batch_equals_queries() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        let terms = ["foo", "foo bar", "bar", "Wort", "Substantiv", "verb", "c", "unknown", "", "die Wort"];
+
+        let mut query = dict.query("");
+        assert_batch_equals_queries(&query, &terms);
+        assert_eq!(query.execute_batch(&terms).unwrap(), dict.query_batch(&terms).unwrap());
+
+        query.set_direction(QueryDirection::ToLeft);
+        assert_batch_equals_queries(&query, &terms);
+
+        query.set_direction(QueryDirection::Bidirectional).exact();
+        assert_batch_equals_queries(&query, &terms);
+
+        query.word().case_sensitive(true);
+        assert_batch_equals_queries(&query, &terms);
+
+        query.case_sensitive(false).any_word_class(&[WordClass::Noun]).limit(2);
+        assert_batch_equals_queries(&query, &terms);
+
+        // Queries without the index
+        query.set_type(QueryType::Wildcard);
+        assert_batch_equals_queries(&query, &["foo*", "*", "?"]);
+
+        query.set_type(QueryType::Boolean);
+        assert_batch_equals_queries(&query, &["foo -bar", "lang:en verb"]);
+    }
+
+    #[test]
+    fn test_batch_placeholders() {
+        let dict = Dict::create("tests/database/test_database_placeholders.txt").unwrap();
+        let terms = ["anrufen", "sb.", "jdn.", "jdn. anrufen", "to call sb.", "sich beeilen", "beeilen"];
+
+        let mut query = dict.query("");
+        assert_batch_equals_queries(&query, &terms);
+
+        query.exact();
+        assert_batch_equals_queries(&query, &terms);
+    }
+}
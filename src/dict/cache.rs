@@ -0,0 +1,317 @@
+//! Shareable `Dict` handle with an optional cache of query results.
+//!
+//! # Example
+//!
+//! ```
+//! use dictcc::Dict;
+//! use dictcc::cache::SharedDict;
+//! use std::thread;
+//!
+//! let dict = SharedDict::with_cache(Dict::create("tests/database/test_database.txt").unwrap(), 100);
+//!
+//! let handles: Vec<_> = (0..4).map(|_| {
+//!     let dict = dict.clone();
+//!
+//!     thread::spawn(move || {
+//!         let query = dict.query("foo");
+//!         dict.execute(&query).unwrap().entries().len()
+//!     })
+//! }).collect();
+//!
+//! for handle in handles {
+//!     assert_eq!(6, handle.join().unwrap());
+//! }
+//!
+//! assert_eq!(4, dict.cache_stats().hits + dict.cache_stats().misses);
+//! ```
+
+use super::*;
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::ops::Deref;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Everything, which determines the matching entries of a `DictQuery`.
+///
+/// The `RelevanceModel`, the offset and the limit are not part of the key,
+/// because the ranking and paging are computed for every execution.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct QueryKey {
+    query_term: String,
+    query_type: QueryType,
+    query_direction: QueryDirection,
+    query_field: QueryField,
+    case_sensitive: bool,
+    match_mode: MatchMode,
+    transliterate: bool,
+    filter: EntryFilter,
+}
+
+impl<'q, 'a, 'b> From<&'q DictQuery<'a, 'b>> for QueryKey {
+    fn from(query: &'q DictQuery<'a, 'b>) -> Self {
+        QueryKey {
+            query_term: query.query_term.to_string(),
+            query_type: query.query_type,
+            query_direction: query.query_direction,
+            query_field: query.query_field,
+            case_sensitive: query.case_sensitive,
+            match_mode: query.match_mode,
+            transliterate: query.transliterate,
+            filter: query.filter.clone(),
+        }
+    }
+}
+
+/// Cache with a bounded number of entries, which evicts the least recently used entry.
+#[derive(Debug)]
+struct LruCache<K: Hash + Eq + Clone, V: Clone> {
+    capacity: usize,
+    /// Incremented on every access
+    clock: u64,
+    /// The values with the time of their last access
+    entries: HashMap<K, (V, u64)>,
+    /// The keys by the time of their last access
+    recency: BTreeMap<u64, K>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity,
+            clock: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.clock += 1;
+
+        match self.entries.get_mut(key) {
+            Some(&mut (ref value, ref mut last_access)) => {
+                self.recency.remove(last_access);
+                self.recency.insert(self.clock, key.clone());
+                *last_access = self.clock;
+
+                Some(value.clone())
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.clock += 1;
+
+        if let Some((_, last_access)) = self.entries.remove(&key) {
+            self.recency.remove(&last_access);
+        } else if self.entries.len() >= self.capacity {
+            let least_recently_used = self.recency.keys().next().cloned();
+
+            if let Some(last_access) = least_recently_used {
+                if let Some(evicted_key) = self.recency.remove(&last_access) {
+                    self.entries.remove(&evicted_key);
+                }
+            }
+        }
+
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(key, (value, self.clock));
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+/// Counters of the query cache of a `SharedDict`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub struct CacheStats {
+    /// The number of queries answered by the cache.
+    pub hits: usize,
+    /// The number of queries, which had to be executed.
+    pub misses: usize,
+    /// The number of cached query results.
+    pub len: usize,
+    /// The maximal number of cached query results.
+    pub capacity: usize,
+}
+
+#[derive(Debug)]
+struct SharedDictInner {
+    dict: Dict,
+    cache: Option<Mutex<LruCache<QueryKey, Arc<QueryMatches>>>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+/// Handle of a `Dict`, which can be cloned cheaply and shared between threads.
+///
+/// Optionally caches the matching entries of the most recently executed queries.
+/// Dereferences to the `Dict`, so queries are created as usual.
+#[derive(Clone, Debug)]
+pub struct SharedDict {
+    inner: Arc<SharedDictInner>,
+}
+
+impl SharedDict {
+    /// Creates a handle without a cache.
+    pub fn new(dict: Dict) -> Self {
+        SharedDict::create(dict, None)
+    }
+
+    /// Creates a handle, which caches the results of at most `capacity` queries.
+    pub fn with_cache(dict: Dict, capacity: usize) -> Self {
+        SharedDict::create(dict, Some(Mutex::new(LruCache::new(capacity))))
+    }
+
+    fn create(dict: Dict, cache: Option<Mutex<LruCache<QueryKey, Arc<QueryMatches>>>>) -> Self {
+        SharedDict {
+            inner: Arc::new(SharedDictInner {
+                dict,
+                cache,
+                hits: AtomicUsize::new(0),
+                misses: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Returns the shared `Dict`.
+    pub fn dict(&self) -> &Dict {
+        &self.inner.dict
+    }
+
+    /// Executes the query using the cache.
+    ///
+    /// Queries of another `Dict` are executed without the cache.
+    pub fn execute<'a, 'b>(&'a self, query: &DictQuery<'a, 'b>) -> DictResult<DictQueryResult<&'a DictEntry>> {
+        let cache = match self.inner.cache {
+            Some(ref cache) if ptr::eq(query.dict, &self.inner.dict) => cache,
+            _ => return query.execute(),
+        };
+
+        let key = QueryKey::from(query);
+
+        let cached = lock(cache).get(&key);
+
+        let query_matches = match cached {
+            Some(query_matches) => {
+                self.inner.hits.fetch_add(1, Ordering::Relaxed);
+                query_matches
+            }
+            None => {
+                self.inner.misses.fetch_add(1, Ordering::Relaxed);
+
+                // The lock is not held during the execution, so other queries are not blocked
+                let query_matches = Arc::new(query.find_matches()?);
+                lock(cache).insert(key, query_matches.clone());
+                query_matches
+            }
+        };
+
+        Ok(query.rank(&query_matches))
+    }
+
+    /// Returns the counters of the cache.
+    pub fn cache_stats(&self) -> CacheStats {
+        let (len, capacity) = match self.inner.cache {
+            Some(ref cache) => {
+                let cache = lock(cache);
+                (cache.len(), cache.capacity)
+            }
+            None => (0, 0),
+        };
+
+        CacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            len,
+            capacity,
+        }
+    }
+
+    /// Removes all cached query results.
+    pub fn clear_cache(&self) {
+        if let Some(ref cache) = self.inner.cache {
+            lock(cache).clear();
+        }
+    }
+}
+
+impl Deref for SharedDict {
+    type Target = Dict;
+
+    fn deref(&self) -> &Dict {
+        self.dict()
+    }
+}
+
+impl From<Dict> for SharedDict {
+    fn from(dict: Dict) -> Self {
+        SharedDict::new(dict)
+    }
+}
+
+/// Locks the cache. A cache poisoned by a panic is still consistent, because it is only modified by `LruCache`.
+fn lock<T>(mutex: &Mutex<T>) -> ::std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_cache() {
+        let mut cache = LruCache::new(2);
+
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(Some(1), cache.get(&"a"));
+
+        // "b" is the least recently used entry
+        cache.insert("c", 3);
+        assert_eq!(None, cache.get(&"b"));
+        assert_eq!(Some(1), cache.get(&"a"));
+        assert_eq!(Some(3), cache.get(&"c"));
+
+        cache.insert("a", 4);
+        assert_eq!(Some(4), cache.get(&"a"));
+        assert_eq!(2, cache.len());
+
+        let mut disabled = LruCache::new(0);
+        disabled.insert("a", 1);
+        assert_eq!(None, disabled.get(&"a"));
+    }
+
+    #[test]
+    fn test_shared_dict_cache() {
+        let dict = SharedDict::with_cache(Dict::create("tests/database/test_database.txt").unwrap(), 10);
+
+        let uncached = dict.query("foo").execute().unwrap();
+
+        let query = dict.query("foo");
+        assert_eq!(uncached, dict.execute(&query).unwrap());
+        assert_eq!(uncached, dict.execute(&query).unwrap());
+
+        let mut other_query = dict.query("foo");
+        other_query.set_direction(QueryDirection::ToLeft);
+        dict.execute(&other_query).unwrap();
+
+        let stats = dict.cache_stats();
+        assert_eq!(1, stats.hits);
+        assert_eq!(2, stats.misses);
+        assert_eq!(2, stats.len);
+    }
+}
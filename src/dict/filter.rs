@@ -0,0 +1,281 @@
+//! Filtering of entries by word class, gender, subject, region, register and word count.
+//!
+//! Used by `DictQuery` to restrict the results of a query.
+
+use super::*;
+
+/// Restricts the word classes of an entry.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum WordClassFilter {
+    /// The entry has at least one of the word classes.
+    Any(Vec<WordClass>),
+    /// The entry has all of the word classes.
+    All(Vec<WordClass>),
+}
+
+impl WordClassFilter {
+    fn matches(&self, word_classes: &[WordClass]) -> bool {
+        match *self {
+            WordClassFilter::Any(ref filter) => filter.iter().any(|class| word_classes.contains(class)),
+            WordClassFilter::All(ref filter) => filter.iter().all(|class| word_classes.contains(class)),
+        }
+    }
+}
+
+/// Inclusive range of the allowed word count of a `DictWord`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Default)]
+pub struct WordCountRange {
+    /// Minimal word count, if any.
+    pub min: Option<u8>,
+    /// Maximal word count, if any.
+    pub max: Option<u8>,
+}
+
+impl WordCountRange {
+    fn matches(&self, word_count: u8) -> bool {
+        match (self.min, self.max) {
+            (Some(min), _) if word_count < min => false,
+            (_, Some(max)) if word_count > max => false,
+            _ => true,
+        }
+    }
+}
+
+/// Filter of `DictEntry`s. All restrictions must be fulfilled by an entry.
+///
+/// The default filter accepts every entry.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct EntryFilter {
+    /// Restricts the word classes of the entry.
+    pub word_classes: Option<WordClassFilter>,
+    /// At least one of the words must have this gender tag.
+    pub gender: Option<Gender>,
+    /// At least one of the words must have any gender tag.
+    pub any_gender: bool,
+    /// The entry must have at least one of these subjects. Empty, if the subject is not restricted.
+    pub subjects: Vec<Subject>,
+    /// Both words must be usable in this region, see `Region::is_compatible`.
+    pub region: Option<Region>,
+    /// Neither word may be vulgar or pejorative, see `Register::is_offensive`.
+    pub family_safe: bool,
+    /// Restricts the word count of the left word.
+    pub left_word_count: WordCountRange,
+    /// Restricts the word count of the right word.
+    pub right_word_count: WordCountRange,
+}
+
+impl EntryFilter {
+    /// Sets the minimal word count of the word on `side`.
+    pub fn set_min_word_count(&mut self, side: EntrySide, min: u8) {
+        for range in self.word_count_ranges(side) {
+            range.min = Some(min);
+        }
+    }
+
+    /// Sets the maximal word count of the word on `side`.
+    pub fn set_max_word_count(&mut self, side: EntrySide, max: u8) {
+        for range in self.word_count_ranges(side) {
+            range.max = Some(max);
+        }
+    }
+
+    fn word_count_ranges(&mut self, side: EntrySide) -> Vec<&mut WordCountRange> {
+        match side {
+            EntrySide::Left => vec![&mut self.left_word_count],
+            EntrySide::Right => vec![&mut self.right_word_count],
+            EntrySide::Both => vec![&mut self.left_word_count, &mut self.right_word_count],
+        }
+    }
+
+    /// Returns true, if the entry fulfills all restrictions of the filter.
+    pub fn matches(&self, entry: &DictEntry) -> bool {
+        if let Some(ref word_class_filter) = self.word_classes {
+            if !word_class_filter.matches(&entry.word_classes) {
+                return false;
+            }
+        }
+
+        if let Some(gender) = self.gender {
+            if !entry.left_word.has_gender(gender) && !entry.right_word.has_gender(gender) {
+                return false;
+            }
+        }
+
+        if self.any_gender && !entry.left_word.has_any_gender() && !entry.right_word.has_any_gender() {
+            return false;
+        }
+
+        if !self.subjects.is_empty() && !self.subjects.iter().any(|subject| entry.subjects.contains(subject)) {
+            return false;
+        }
+
+        if let Some(region) = self.region {
+            if !region.is_compatible(&entry.left_word) || !region.is_compatible(&entry.right_word) {
+                return false;
+            }
+        }
+
+        if self.family_safe && entry.registers().iter().any(|register| register.is_offensive()) {
+            return false;
+        }
+
+        self.left_word_count.matches(entry.left_word.word_count)
+            && self.right_word_count.matches(entry.right_word.word_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filtered(dict: &Dict, filter: &EntryFilter) -> Vec<String> {
+        dict.entries.iter()
+            .filter(|entry| filter.matches(entry))
+            .map(|entry| entry.left_word.plain_word())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_filter() {
+        let dict = Dict::create("tests/database/test_database_filter.txt").unwrap();
+
+        assert_eq!(dict.entries.len(), filtered(&dict, &EntryFilter::default()).len());
+    }
+
+    #[test]
+    fn test_word_class_filter() {
+        let dict = Dict::create("tests/database/test_database_filter.txt").unwrap();
+        let mut filter = EntryFilter {
+            word_classes: Some(WordClassFilter::Any(vec![WordClass::Verb])),
+            ..Default::default()
+        };
+        assert_eq!(vec!["Wort &", "Verb"], filtered(&dict, &filter));
+
+        filter.word_classes = Some(WordClassFilter::All(vec![WordClass::Verb, WordClass::Past]));
+        assert_eq!(vec!["Wort &"], filtered(&dict, &filter));
+    }
+
+    #[test]
+    fn test_gender_filter() {
+        let dict = Dict::create("tests/database/test_database_filter.txt").unwrap();
+        let mut filter = EntryFilter {
+            gender: Some(Gender::Feminine),
+            ..Default::default()
+        };
+        assert_eq!(vec!["Wort &", "Wort"], filtered(&dict, &filter));
+
+        filter.gender = Some(Gender::Masculine);
+        assert_eq!(vec!["Hund"], filtered(&dict, &filter));
+
+        filter.gender = Some(Gender::Plural);
+        assert_eq!(vec!["Leute"], filtered(&dict, &filter));
+
+        filter.gender = Some(Gender::Neuter);
+        assert!(filtered(&dict, &filter).is_empty());
+
+        let filter = EntryFilter {
+            any_gender: true,
+            ..Default::default()
+        };
+        assert_eq!(vec!["Wort &", "Wort", "Hund", "Leute"], filtered(&dict, &filter));
+    }
+
+    #[test]
+    fn test_word_count_filter() {
+        let dict = Dict::create("tests/database/test_database_filter.txt").unwrap();
+        let mut filter = EntryFilter::default();
+
+        // Optional parts are counted, e.g. `(optionales) Wort &`
+        filter.set_min_word_count(EntrySide::Both, 3);
+        assert_eq!(vec!["Wort &", "foo bar baz", "foo bar baz qux"], filtered(&dict, &filter));
+
+        filter.set_max_word_count(EntrySide::Left, 3);
+        assert_eq!(vec!["Wort &", "foo bar baz"], filtered(&dict, &filter));
+    }
+
+    #[test]
+    fn test_subject_filter() {
+        let dict = Dict::create("tests/database/test_database_subjects.txt").unwrap();
+        let mut filter = EntryFilter {
+            subjects: vec![Subject::Automotive],
+            ..Default::default()
+        };
+
+        // The subject column with and without brackets
+        assert_eq!(vec!["Lastwagen", "Lastwagen"], filtered(&dict, &filter));
+
+        filter.subjects.push(Subject::Zoology);
+        assert_eq!(vec!["Lastwagen", "Lastwagen", "Kiwi"], filtered(&dict, &filter));
+
+        // A comment consisting of a subject label
+        filter.subjects = vec![Subject::Medicine];
+        assert_eq!(vec!["Spritze"], filtered(&dict, &filter));
+    }
+
+    #[test]
+    fn test_region_filter() {
+        let dict = Dict::create("tests/database/test_database_regions.txt").unwrap();
+        let right_words = |filter: &EntryFilter| -> Vec<String> {
+            dict.entries.iter()
+                .filter(|entry| filter.matches(entry) && entry.left_word.plain_word() == "Lastwagen")
+                .map(|entry| entry.right_word.plain_word())
+                .collect()
+        };
+
+        let mut filter = EntryFilter {
+            region: Some(Region::British),
+            ..Default::default()
+        };
+        assert_eq!(vec!["lorry"], right_words(&filter));
+
+        filter.region = Some(Region::American);
+        assert_eq!(vec!["truck"], right_words(&filter));
+
+        // Words of other languages are common to all regions
+        filter.region = Some(Region::Austrian);
+        assert_eq!(vec!["lorry", "truck"], right_words(&filter));
+        assert_eq!(vec!["Lastwagen", "Lastwagen", "Kiwi", "Tomate", "Paradeiser"], filtered(&dict, &filter));
+
+        filter.region = Some(Region::Swiss);
+        assert_eq!(vec!["Lastwagen", "Lastwagen", "Kiwi", "Tomate"], filtered(&dict, &filter));
+
+        // Multi-word region labels
+        filter.region = Some(Region::NewZealand);
+        assert!(filtered(&dict, &filter).contains(&"Kiwi".to_string()));
+        filter.region = Some(Region::British);
+        assert!(!filtered(&dict, &filter).contains(&"Kiwi".to_string()));
+    }
+
+    #[test]
+    fn test_family_safe_filter() {
+        let dict = Dict::create("tests/database/test_database_registers.txt").unwrap();
+        let filter = EntryFilter {
+            family_safe: true,
+            ..Default::default()
+        };
+
+        // Pejorative and vulgar words are hidden, colloquial words are not offensive
+        assert_eq!(vec!["Polizist", "Bulle", "Kiwi"], filtered(&dict, &filter));
+    }
+
+    #[test]
+    fn test_combined_filter() {
+        let dict = Dict::create("tests/database/test_database_filter.txt").unwrap();
+        let mut filter = EntryFilter {
+            word_classes: Some(WordClassFilter::Any(vec![WordClass::Noun])),
+            ..Default::default()
+        };
+
+        filter.set_max_word_count(EntrySide::Both, 1);
+        assert_eq!(vec!["Substantiv", "foo", "Hund", "Leute"], filtered(&dict, &filter));
+
+        filter.any_gender = true;
+        assert_eq!(vec!["Hund", "Leute"], filtered(&dict, &filter));
+
+        filter.set_max_word_count(EntrySide::Both, 2);
+        assert_eq!(vec!["Wort", "Hund", "Leute"], filtered(&dict, &filter));
+
+        filter.gender = Some(Gender::Feminine);
+        assert_eq!(vec!["Wort"], filtered(&dict, &filter));
+    }
+}
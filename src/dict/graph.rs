@@ -0,0 +1,310 @@
+//! Bipartite graph of the translations in a `Dict`.
+//!
+//! The nodes of the graph are the words of both languages, normalized to their lowercase plain word
+//! (see `DictWord::plain_word`). Every `DictEntry` is an edge between its left and its right word.
+//!
+//! # Example
+//!
+//! ```
+//! use dictcc::{Dict, Language};
+//!
+//! let dict = Dict::create("tests/database/test_database.txt").unwrap();
+//! let graph = dict.graph();
+//!
+//! for start in graph.find("foo", Some(&Language::DE)) {
+//!     for related in graph.related(start, 2) {
+//!         println!("{}{} ({})", "  ".repeat(related.depth), related.node.word, related.node.language);
+//!     }
+//! }
+//! ```
+
+use super::*;
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+/// A normalized word of one language of the `Dict`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GraphNode {
+    /// The lowercase plain word.
+    pub word: String,
+    /// The language of the word.
+    pub language: Language,
+}
+
+/// A term of the neighbourhood of a start term, see `TranslationGraph::related`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RelatedTerm<'a> {
+    /// The related term.
+    pub node: &'a GraphNode,
+    /// The number of translations between the start term and this term.
+    pub depth: usize,
+    /// The index of the term (in the neighbourhood), through which this term was reached.
+    /// `None` for the start term.
+    pub parent: Option<usize>,
+}
+
+/// Bipartite graph of the words of a `Dict`, connected by their translations.
+///
+/// Created by `Dict::graph`.
+#[derive(Clone, Debug)]
+pub struct TranslationGraph<'a> {
+    nodes: Vec<GraphNode>,
+    node_indices: HashMap<GraphNode, usize>,
+    /// The neighbours of every node, together with the entry connecting them
+    edges: Vec<Vec<(usize, &'a DictEntry)>>,
+}
+
+impl Dict {
+    /// Builds the translation graph of all entries.
+    pub fn graph(&self) -> TranslationGraph<'_> {
+        let mut graph = TranslationGraph {
+            nodes: vec![],
+            node_indices: HashMap::new(),
+            edges: vec![],
+        };
+
+        for entry in &self.entries {
+            let left = graph.insert_node(&entry.left_word, &self.languages.left_language);
+            let right = graph.insert_node(&entry.right_word, &self.languages.right_language);
+
+            if let (Some(left), Some(right)) = (left, right) {
+                graph.edges[left].push((right, entry));
+                graph.edges[right].push((left, entry));
+            }
+        }
+
+        graph
+    }
+}
+
+impl<'a> TranslationGraph<'a> {
+    fn insert_node(&mut self, word: &DictWord, language: &Language) -> Option<usize> {
+        let node = GraphNode {
+            word: word.plain_word().to_lowercase(),
+            language: language.clone(),
+        };
+
+        if node.word.is_empty() {
+            return None;
+        }
+
+        if let Some(&index) = self.node_indices.get(&node) {
+            return Some(index);
+        }
+
+        let index = self.nodes.len();
+
+        self.node_indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+
+        Some(index)
+    }
+
+    fn index(&self, node: &GraphNode) -> Option<usize> {
+        self.node_indices.get(node).cloned()
+    }
+
+    /// Returns all nodes of the graph.
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// Returns the nodes of `word` in `language` or in both languages, if `language` is `None`.
+    pub fn find(&self, word: &str, language: Option<&Language>) -> Vec<&GraphNode> {
+        let word = word.trim().to_lowercase();
+
+        self.nodes.iter()
+            .filter(|node| node.word == word && match language {
+                Some(language) => node.language == *language,
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Returns the translations of `node`, together with the entries connecting them.
+    pub fn neighbours(&self, node: &GraphNode) -> Vec<(&GraphNode, &'a DictEntry)> {
+        match self.index(node) {
+            Some(index) => self.edges[index].iter()
+                .map(|&(neighbour, entry)| (&self.nodes[neighbour], entry))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Returns the terms reachable from `start` with at most `max_depth` translations.
+    ///
+    /// The terms are in breadth-first order and form a tree (see `RelatedTerm::parent`), starting with `start`.
+    pub fn related(&self, start: &GraphNode, max_depth: usize) -> Vec<RelatedTerm<'_>> {
+        let start = match self.index(start) {
+            Some(start) => start,
+            None => return vec![],
+        };
+
+        let mut related = vec![];
+        // The position of every visited node in `related`
+        let mut positions = HashMap::new();
+
+        self.breadth_first_search(start, |index, depth, parent| {
+            if depth > max_depth {
+                return false;
+            }
+
+            positions.insert(index, related.len());
+
+            related.push(RelatedTerm {
+                node: &self.nodes[index],
+                depth,
+                parent: parent.and_then(|parent| positions.get(&parent).cloned()),
+            });
+
+            true
+        });
+
+        related
+    }
+
+    /// Returns all nodes connected to `node` by any number of translations, including `node`.
+    pub fn component(&self, node: &GraphNode) -> Vec<&GraphNode> {
+        let start = match self.index(node) {
+            Some(start) => start,
+            None => return vec![],
+        };
+
+        let mut component = vec![];
+
+        self.breadth_first_search(start, |index, _, _| {
+            component.push(&self.nodes[index]);
+            true
+        });
+
+        component
+    }
+
+    /// Partitions the graph into its connected components.
+    ///
+    /// The components are ordered by descending size.
+    pub fn connected_components(&self) -> Vec<Vec<&GraphNode>> {
+        let mut assigned = vec![false; self.nodes.len()];
+        let mut components: Vec<Vec<&GraphNode>> = vec![];
+
+        for start in 0..self.nodes.len() {
+            if assigned[start] {
+                continue;
+            }
+
+            let mut component = vec![];
+
+            self.breadth_first_search(start, |index, _, _| {
+                assigned[index] = true;
+                component.push(&self.nodes[index]);
+                true
+            });
+
+            components.push(component);
+        }
+
+        components.sort_by_key(|component| Reverse(component.len()));
+
+        components
+    }
+
+    /// Returns the shortest chain of translations from `from` to `to`, including both nodes.
+    ///
+    /// Returns `None`, if the nodes are not connected.
+    pub fn path(&self, from: &GraphNode, to: &GraphNode) -> Option<Vec<&GraphNode>> {
+        let (from, to) = match (self.index(from), self.index(to)) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return None,
+        };
+
+        let mut parents = vec![None; self.nodes.len()];
+        let mut found = false;
+
+        self.breadth_first_search(from, |index, _, parent| {
+            if found {
+                return false;
+            }
+
+            parents[index] = parent;
+            found = index == to;
+
+            true
+        });
+
+        if !found {
+            return None;
+        }
+
+        let mut path = vec![&self.nodes[to]];
+        let mut current = to;
+
+        while let Some(parent) = parents[current] {
+            path.push(&self.nodes[parent]);
+            current = parent;
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Visits all nodes reachable from `start` in breadth-first order.
+    ///
+    /// `visit` is called with the node, its depth and its parent.
+    /// The neighbours of a node are not visited, if `visit` returns false.
+    fn breadth_first_search<F>(&self, start: usize, mut visit: F)
+        where F: FnMut(usize, usize, Option<usize>) -> bool {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::new();
+
+        visited[start] = true;
+        queue.push_back((start, 0, None));
+
+        while let Some((index, depth, parent)) = queue.pop_front() {
+            if !visit(index, depth, parent) {
+                continue;
+            }
+
+            for &(neighbour, _) in &self.edges[index] {
+                if !visited[neighbour] {
+                    visited[neighbour] = true;
+                    queue.push_back((neighbour, depth + 1, Some(index)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(word: &str, language: Language) -> GraphNode {
+        GraphNode {
+            word: word.to_string(),
+            language,
+        }
+    }
+
+    #[test]
+    fn test_translation_graph() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        let graph = dict.graph();
+
+        let start = node("a", Language::DE);
+
+        let related: Vec<_> = graph.related(&start, 2).into_iter()
+            .map(|term| (term.node.word.as_str(), term.depth, term.parent))
+            .collect();
+        assert_eq!(vec![("a", 0, None), ("c", 1, Some(0))], related);
+
+        assert_eq!(2, graph.component(&start).len());
+        assert_eq!(None, graph.path(&start, &node("b", Language::EN)));
+        assert_eq!(Some(vec![&start, &node("c", Language::EN)]), graph.path(&start, &node("c", Language::EN)));
+
+        let components = graph.connected_components();
+        assert_eq!(graph.nodes().len(), components.iter().map(|component| component.len()).sum());
+    }
+}
@@ -6,6 +6,8 @@
 //! 1. By word count
 //! 2. By word class group.
 //!
+//! Within a group, the entries are ranked by their relevance (see `relevance`).
+//!
 //! # Example Output
 //!
 //! ```ignore
@@ -63,18 +65,51 @@ impl<'a> From<&'a [WordClass]> for WordClassesGroup {
 ///
 /// Implements Display using a formatted and aligned table.
 #[derive(Clone, Eq, PartialEq, Debug)]
-pub struct DictQueryResultGrouped {
-    word_count_groups: Vec<DictEntryWordCountGroup>
+pub struct DictQueryResultGrouped<E: Borrow<DictEntry>> {
+    word_count_groups: Vec<DictEntryWordCountGroup<E>>
 }
 
-impl DictQueryResultGrouped {
+impl<E: Borrow<DictEntry>> DictQueryResultGrouped<E> {
     /// Returns a slice of `DictEntryWordCountGroup`.
-    pub fn word_count_groups(&self) -> &[DictEntryWordCountGroup] {
+    pub fn word_count_groups(&self) -> &[DictEntryWordCountGroup<E>] {
         &self.word_count_groups
     }
+
+    /// Set whether the romanized form of Cyrillic and Greek words is shown next to the words (default: `false`).
+    pub fn set_show_romanized(&mut self, show_romanized: bool) -> &mut Self {
+        for word_class_group in self.word_class_groups_mut() {
+            word_class_group.show_romanized = show_romanized;
+        }
+
+        self
+    }
+
+    /// Set whether German nouns are shown with their definite articles, e.g. `die Wohnung {f}` (default: `false`).
+    pub fn set_show_articles(&mut self, show_articles: bool) -> &mut Self {
+        for word_class_group in self.word_class_groups_mut() {
+            word_class_group.show_articles = show_articles;
+        }
+
+        self
+    }
+
+    /// Set whether placeholders are shown expanded, e.g. `jemandem helfen` instead of `jdm. helfen` (default: `false`).
+    pub fn set_expand_placeholders(&mut self, expand_placeholders: bool) -> &mut Self {
+        for word_class_group in self.word_class_groups_mut() {
+            word_class_group.expand_placeholders = expand_placeholders;
+        }
+
+        self
+    }
+
+    fn word_class_groups_mut(&mut self) -> Vec<&mut DictEntryWordClassGroup<E>> {
+        self.word_count_groups.iter_mut()
+            .flat_map(|word_count_group| word_count_group.word_class_groups.iter_mut())
+            .collect()
+    }
 }
 
-impl Display for DictQueryResultGrouped {
+impl<E: Borrow<DictEntry>> Display for DictQueryResultGrouped<E> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         use prettytable::format::LinePosition::*;
         use prettytable::format::LineSeparator;
@@ -99,72 +134,101 @@ impl Display for DictQueryResultGrouped {
 }
 
 
-impl From<DictQueryResult> for DictQueryResultGrouped {
-    fn from(query_result: DictQueryResult) -> Self {
+/// An entry of a `DictQueryResult` with its match and relevance.
+type MatchedEntry<E> = (E, EntryMatch, Relevance);
+
+impl<E: Borrow<DictEntry>> From<DictQueryResult<E>> for DictQueryResultGrouped<E> {
+    /// Groups the entries by word count and word class group.
+    ///
+    /// Within a group, the entries are ranked by descending relevance.
+    /// Entries with the same relevance are sorted alphabetically by the word in the query direction.
+    fn from(query_result: DictQueryResult<E>) -> Self {
         fn group_entries<G, I>(mut entries: Vec<I>, by: fn(&I) -> G)
                                -> GroupBy<G, IntoIter<I>, fn(&I) -> G>
             where G: Ord {
-            entries.sort_unstable_by_key(by);
+            // Stable sort, so that the order within a group is kept
+            entries.sort_by_key(by);
 
             entries.into_iter().group_by(by)
         }
 
         let query_direction = query_result.query_direction;
-        let entries = query_result.entries;
-
-        let get_word_count: fn(&DictEntry) -> u8 = match query_direction {
-            QueryDirection::ToRight => |entry: &DictEntry| {
-                entry.left_word.word_count
-            },
-            QueryDirection::Bidirectional => DictEntry::get_max_word_count,
-            QueryDirection::ToLeft => |entry: &DictEntry| {
-                entry.right_word.word_count
-            },
+        let entries: Vec<MatchedEntry<E>> = query_result.entries.into_iter()
+            .zip(query_result.matches)
+            .zip(query_result.relevances)
+            .map(|((entry, entry_match), relevance)| (entry, entry_match, relevance))
+            .collect();
+
+        fn left_word_count<E: Borrow<DictEntry>>(matched_entry: &MatchedEntry<E>) -> u8 {
+            matched_entry.0.borrow().left_word.word_count
+        }
+
+        fn max_word_count<E: Borrow<DictEntry>>(matched_entry: &MatchedEntry<E>) -> u8 {
+            matched_entry.0.borrow().get_max_word_count()
+        }
+
+        fn right_word_count<E: Borrow<DictEntry>>(matched_entry: &MatchedEntry<E>) -> u8 {
+            matched_entry.0.borrow().right_word.word_count
+        }
+
+        let get_word_count: fn(&MatchedEntry<E>) -> u8 = match query_direction {
+            QueryDirection::ToRight => left_word_count,
+            QueryDirection::Bidirectional => max_word_count,
+            QueryDirection::ToLeft => right_word_count,
         };
 
         let word_count_group_by =
             group_entries(entries, get_word_count);
 
         let grouped_entries: Vec<_> = word_count_group_by.into_iter().map(|(word_count, same_word_count_group)| {
-            let same_word_count_pairs: Vec<(WordClassesGroup, DictEntry)> = same_word_count_group
-                .map(|entry| {
-                    let word_classes_group: WordClassesGroup = entry.word_classes.as_slice().into();
+            let same_word_count_pairs: Vec<(WordClassesGroup, MatchedEntry<E>)> = same_word_count_group
+                .map(|matched_entry| {
+                    let word_classes_group: WordClassesGroup = matched_entry.0.borrow().word_classes.as_slice().into();
 
-                    (word_classes_group, entry)
+                    (word_classes_group, matched_entry)
                 })
                 .collect();
 
             let word_class_group_by =
-                group_entries(same_word_count_pairs, |&(word_class_group, _)| word_class_group);
+                group_entries(same_word_count_pairs, |pair| pair.0);
 
-            let vec_word_class_group: Vec<DictEntryWordClassGroup> =
+            let vec_word_class_group: Vec<DictEntryWordClassGroup<E>> =
                 word_class_group_by.into_iter().map(|(word_class_group, entries_group)| {
-                    let mut entries: Vec<DictEntry> = entries_group.map(|(_, entry)| entry).collect();
+                    let mut matched_entries: Vec<MatchedEntry<E>> = entries_group.map(|(_, matched_entry)| matched_entry).collect();
 
-                    let cmp_left = |left_entry: &DictEntry, right_entry: &DictEntry| {
-                        let left = &left_entry.left_word.indexed_word;
-                        let right = &right_entry.left_word.indexed_word;
+                    let indexed_word = |matched_entry: &MatchedEntry<E>| {
+                        let entry = matched_entry.0.borrow();
 
-                        left.cmp(right)
+                        match query_direction {
+                            QueryDirection::ToRight |
+                            QueryDirection::Bidirectional => entry.left_word.indexed_word.clone(),
+                            QueryDirection::ToLeft => entry.right_word.indexed_word.clone(),
+                        }
                     };
 
-                    let cmp_right = |left_entry: &DictEntry, right_entry: &DictEntry| {
-                        let left = &left_entry.right_word.indexed_word;
-                        let right = &right_entry.right_word.indexed_word;
+                    matched_entries.sort_by(|left, right| {
+                        right.2.cmp(&left.2).then_with(|| indexed_word(left).cmp(&indexed_word(right)))
+                    });
 
-                        left.cmp(right)
-                    };
+                    let mut entries = vec![];
+                    let mut matches = vec![];
+                    let mut relevances = vec![];
 
-                    match query_direction {
-                        QueryDirection::ToRight |
-                        QueryDirection::Bidirectional => entries.sort_by(cmp_left),
-                        QueryDirection::ToLeft => entries.sort_by(cmp_right),
-                    };
+                    for (entry, entry_match, relevance) in matched_entries {
+                        entries.push(entry);
+                        matches.push(entry_match);
+                        relevances.push(relevance);
+                    }
 
                     DictEntryWordClassGroup {
                         word_count,
                         word_class_group,
                         entries,
+                        matches,
+                        relevances,
+                        show_romanized: false,
+                        show_articles: false,
+                        expand_placeholders: false,
                     }
                 }).collect();
 
@@ -184,14 +248,14 @@ impl From<DictQueryResult> for DictQueryResultGrouped {
 ///
 /// Implements Display using a formatted and aligned table.
 #[derive(Clone, Eq, PartialEq, Debug)]
-pub struct DictEntryWordCountGroup {
+pub struct DictEntryWordCountGroup<E: Borrow<DictEntry>> {
     word_count: u8,
-    word_class_groups: Vec<DictEntryWordClassGroup>,
+    word_class_groups: Vec<DictEntryWordClassGroup<E>>,
 }
 
-impl DictEntryWordCountGroup {
+impl<E: Borrow<DictEntry>> DictEntryWordCountGroup<E> {
     /// Returns a slice of `DictEntryWordClassGroup`.
-    pub fn word_class_groups(&self) -> &[DictEntryWordClassGroup] {
+    pub fn word_class_groups(&self) -> &[DictEntryWordClassGroup<E>] {
         &self.word_class_groups
     }
 
@@ -201,7 +265,7 @@ impl DictEntryWordCountGroup {
     }
 }
 
-impl Display for DictEntryWordCountGroup {
+impl<E: Borrow<DictEntry>> Display for DictEntryWordCountGroup<E> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         use prettytable::format::LinePosition::*;
         use prettytable::format::LineSeparator;
@@ -229,18 +293,33 @@ impl Display for DictEntryWordCountGroup {
 ///
 /// Implements Display using a formatted and aligned table.
 #[derive(Clone, Eq, PartialEq, Debug)]
-pub struct DictEntryWordClassGroup {
+pub struct DictEntryWordClassGroup<E: Borrow<DictEntry>> {
     word_count: u8,
     word_class_group: WordClassesGroup,
-    entries: Vec<DictEntry>,
+    entries: Vec<E>,
+    matches: Vec<EntryMatch>,
+    relevances: Vec<Relevance>,
+    show_romanized: bool,
+    show_articles: bool,
+    expand_placeholders: bool,
 }
 
-impl DictEntryWordClassGroup {
+impl<E: Borrow<DictEntry>> DictEntryWordClassGroup<E> {
     /// Returns a slice of entries in this group.
-    pub fn entries(&self) -> &[DictEntry] {
+    pub fn entries(&self) -> &[E] {
         &self.entries
     }
 
+    /// Returns where the query matched every entry, in the same order as `entries`.
+    pub fn matches(&self) -> &[EntryMatch] {
+        &self.matches
+    }
+
+    /// Returns the relevance of every entry, in the same order as `entries`.
+    pub fn relevances(&self) -> &[Relevance] {
+        &self.relevances
+    }
+
     /// The word count of this group.
     pub fn word_count(&self) -> u8 {
         self.word_count
@@ -253,19 +332,50 @@ impl DictEntryWordClassGroup {
 }
 
 
-impl Display for DictEntryWordClassGroup {
+impl<E: Borrow<DictEntry>> Display for DictEntryWordClassGroup<E> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use colored::Colorize;
         use prettytable;
         use prettytable::Table;
 
+        let format_word = |word: &DictWord, spans: &[Range<usize>]| {
+            let highlighted = if self.expand_placeholders {
+                word.to_highlighted_expanded_string(spans)
+            } else {
+                word.to_highlighted_string(spans)
+            };
+
+            let highlighted = if self.show_articles && !word.articles().is_empty() {
+                format!("{} {}", word.articles().join("/"), highlighted)
+            } else {
+                highlighted
+            };
+
+            match word.romanized() {
+                Some(romanized) if self.show_romanized => format!("{} {}", highlighted, format!("/{}/", romanized).dimmed()),
+                _ => highlighted,
+            }
+        };
+
+        // The register column is only shown, if any entry of the group has a register
+        let show_registers = self.entries.iter().any(|entry| !entry.borrow().registers().is_empty());
+
         // TODO: word classes filter (redundant classes)
-        let entry_rows: Vec<_> = self.entries.iter().map(|entry| {
-            let left = &entry.left_word.to_colored_string();
-            let right = &entry.right_word.to_colored_string();
+        let entry_rows: Vec<_> = self.entries.iter().zip(&self.matches).map(|(entry, entry_match)| {
+            let entry = entry.borrow();
+
+            let left = &format_word(&entry.left_word, entry_match.left_spans());
+            let right = &format_word(&entry.right_word, entry_match.right_spans());
 
             let word_classes = &entry.word_classes.iter().map(|word_class| format!("{:?}", word_class)).collect::<Vec<_>>().join(", ");
 
-            row![left, right, word_classes]
+            if show_registers {
+                let registers = &entry.registers().iter().map(|register| register.to_string()).collect::<Vec<_>>().join(", ");
+
+                row![left, right, word_classes, registers]
+            } else {
+                row![left, right, word_classes]
+            }
         }).collect();
 
         let mut entry_table = Table::init(entry_rows);
@@ -291,3 +401,21 @@ impl Display for DictEntryWordClassGroup {
         f.write_str(&complete_table.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grouped_ranking() {
+        let dict = Dict::create("tests/database/test_database_ranking.txt").unwrap();
+
+        let grouped = dict.query("home").execute().unwrap().into_grouped();
+        let group = &grouped.word_count_groups()[0].word_class_groups()[0];
+
+        // `home [house]` has a comment penalty, so it is ranked below `home`, although `Heim` < `Zuhause`
+        let left_words: Vec<_> = group.entries().iter().map(|entry| entry.left_word.plain_word()).collect();
+        assert_eq!(vec!["Zuhause", "Heim"], left_words);
+        assert!(group.relevances()[0] > group.relevances()[1]);
+    }
+}
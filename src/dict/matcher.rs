@@ -0,0 +1,715 @@
+//! Compiled matching logic of a `DictQuery`.
+
+use super::*;
+
+use dict::phonetic::{self, PhoneticAlgorithm};
+use parse::query_ast::{QueryExpr, QueryNode};
+use parse::word_ast::WordNode;
+use std::cmp::{max, min};
+
+/// Matches a single query term against the words of an entry.
+#[derive(Debug)]
+struct TermMatcher {
+    /// Matches the indexed word and the comments
+    regex: Regex,
+    /// Matches the acronyms, which are always compared as a whole
+    keyword_regex: Regex,
+    /// Matches the romanized words, if the query is transliterated
+    romanized_regex: Option<Regex>,
+    /// The phonetic codes of the query for the algorithm of each language, if the query is phonetic
+    phonetic_codes: Option<Vec<(PhoneticAlgorithm, Vec<Vec<String>>)>>,
+    query_field: QueryField,
+    case_sensitive: bool,
+    match_mode: MatchMode,
+}
+
+impl TermMatcher {
+    fn new(query_term: &str, query_type: QueryType, query_field: QueryField, query: &DictQuery) -> DictResult<Self> {
+        let case_sensitive = query.case_sensitive;
+
+        // The first capture group always contains the matched term, see `find_terms`
+        let keyword_pattern = match query_type {
+            QueryType::Word | QueryType::Exact | QueryType::Boolean | QueryType::Phonetic => format!(r"^({})$", escape(query_term)),
+            QueryType::Regex => format!(r"^({})$", query_term),
+            QueryType::Wildcard => format!(r"^({})$", glob_to_regex(query_term)),
+        };
+
+        let build_pattern = |query_term: &str| match query_type {
+            QueryType::Word | QueryType::Boolean | QueryType::Phonetic => format!(r"(?:^|\s|-)({})(?:$|\s|-)", escape(query_term)),
+            QueryType::Exact => format!(r"^({})$", escape(query_term)),
+            QueryType::Regex => format!(r"^({})$", query_term),
+            QueryType::Wildcard => format!(r"^({})$", glob_to_regex(query_term)),
+        };
+
+        let languages = query.dict.get_language_pair();
+
+        let phonetic_codes = if query_type == QueryType::Phonetic {
+            let mut phonetic_codes: Vec<(PhoneticAlgorithm, Vec<Vec<String>>)> = vec![];
+
+            for language in &[&languages.left_language, &languages.right_language] {
+                let algorithm = PhoneticAlgorithm::for_language(language);
+
+                if phonetic_codes.iter().all(|&(other_algorithm, _)| other_algorithm != algorithm) {
+                    // Cyrillic and Greek words are encoded in their romanized form
+                    let term = transliterate::romanize(query_term, language).unwrap_or_else(|| query_term.to_string());
+                    phonetic_codes.push((algorithm, algorithm.encode_words(&term)));
+                }
+            }
+
+            Some(phonetic_codes)
+        } else {
+            None
+        };
+        let has_romanized_words = transliterate::has_romanization(&languages.left_language)
+            || transliterate::has_romanization(&languages.right_language);
+
+        // The romanized words are matched without diacritics
+        let romanized_regex = if query.transliterate && has_romanized_words {
+            let romanized_pattern = build_pattern(&normalize::fold_diacritics(query_term));
+            Some(RegexBuilder::new(&romanized_pattern).case_insensitive(!case_sensitive).build()?)
+        } else {
+            None
+        };
+
+        Ok(TermMatcher {
+            regex: RegexBuilder::new(&build_pattern(query_term)).case_insensitive(!case_sensitive).build()?,
+            keyword_regex: RegexBuilder::new(&keyword_pattern).case_insensitive(!case_sensitive).build()?,
+            romanized_regex,
+            phonetic_codes,
+            query_field,
+            case_sensitive,
+            match_mode: query.match_mode,
+        })
+    }
+
+    fn word_matches(&self, word: &DictWord) -> bool {
+        // The indexed fields are in lowercase, so case sensitive queries
+        // have to match against the original text of the word.
+        let indexed_word_matches = || match self.match_mode {
+            // Independent of the match mode, the phonetic codes are only indexed with the optional parts
+            _ if self.phonetic_codes.is_some() => match self.phonetic_word_codes(word) {
+                Some(codes) => !phonetic::find_matches(codes, &word.indexed_phonetic).is_empty(),
+                None => false,
+            },
+            MatchMode::WithOptionalParts => if self.case_sensitive {
+                self.regex.is_match(&word.word_nodes.build_unbracketed_word())
+            } else {
+                self.regex.is_match(&word.indexed_word)
+            },
+            MatchMode::PlainWord => self.regex.is_match(&word.plain_word()),
+            MatchMode::AnyExpansion => word.expansions().iter().any(|expansion| self.regex.is_match(expansion)),
+        };
+
+        // Placeholders are not part of the indexed word, so the full form is matched as well, e.g. `jdn. anrufen`.
+        // Independent of the match mode, the full form includes the optional parts.
+        let full_word_matches = || match word.indexed_full_word {
+            Some(_) if self.phonetic_codes.is_some() => false,
+            Some(_) if self.case_sensitive => self.regex.is_match(&word.word_nodes.build_full_word()),
+            Some(ref indexed_full_word) => self.regex.is_match(indexed_full_word),
+            None => false,
+        };
+
+        // Independent of the match mode, the romanized word is only indexed with its optional parts
+        let romanized_word_matches = || match self.romanized_regex {
+            Some(ref romanized_regex) => {
+                let romanized = if self.case_sensitive {
+                    &word.unbracketed_romanized
+                } else {
+                    &word.indexed_romanized
+                };
+
+                match *romanized {
+                    Some(ref romanized) => romanized_regex.is_match(romanized),
+                    None => false,
+                }
+            }
+            None => false,
+        };
+
+        let acronyms_match = || if self.case_sensitive {
+            word.acronyms().iter().any(|acronym| self.keyword_regex.is_match(acronym))
+        } else {
+            word.indexed_acronyms.iter().any(|acronym| self.keyword_regex.is_match(acronym))
+        };
+
+        let comments_match = || if self.case_sensitive {
+            word.comments().iter().any(|comment| self.regex.is_match(comment))
+        } else {
+            word.indexed_comments.iter().any(|comment| self.regex.is_match(comment))
+        };
+
+        match self.query_field {
+            QueryField::IndexedWord => indexed_word_matches() || full_word_matches() || romanized_word_matches(),
+            QueryField::Acronyms => acronyms_match(),
+            QueryField::Comments => comments_match(),
+            QueryField::All => indexed_word_matches() || full_word_matches() || romanized_word_matches() || acronyms_match() || comments_match(),
+        }
+    }
+
+    /// The phonetic codes of the query for the phonetic algorithm of `word`.
+    fn phonetic_word_codes(&self, word: &DictWord) -> Option<&[Vec<String>]> {
+        self.phonetic_codes.as_ref()
+            .and_then(|phonetic_codes| phonetic_codes.iter().find(|&&(algorithm, _)| algorithm == word.phonetic_algorithm))
+            .map(|(_, codes)| codes.as_slice())
+    }
+
+    /// The byte ranges of the matches within the displayed word.
+    ///
+    /// Uses the same fields and match mode as `word_matches`.
+    fn word_spans(&self, word: &DictWord) -> Vec<Range<usize>> {
+        let mut spans = vec![];
+
+        let search_indexed_word = matches!(self.query_field, QueryField::IndexedWord | QueryField::All);
+
+        let with_optional_parts: Vec<bool> = word.word_nodes.iter()
+            .map(|node| matches!(*node, WordNode::Word(_) | WordNode::Round(_)))
+            .collect();
+
+        if let (true, Some(codes)) = (search_indexed_word, self.phonetic_word_codes(word)) {
+            // The tokens of the projection correspond to the tokens of the indexed word
+            let projection = Projection::new(word, &with_optional_parts);
+            let tokens = phonetic::tokenize(&projection.text);
+
+            if tokens.len() == word.indexed_phonetic.len() {
+                for start in phonetic::find_matches(codes, &word.indexed_phonetic) {
+                    let span = tokens[start].start..tokens[start + codes.len() - 1].end;
+                    spans.extend(projection.to_display_spans(span));
+                }
+            }
+        } else if search_indexed_word {
+            let mut selections = match self.match_mode {
+                MatchMode::WithOptionalParts => vec![with_optional_parts],
+                MatchMode::PlainWord => vec![word.word_nodes.iter().map(|node| matches!(*node, WordNode::Word(_))).collect()],
+                MatchMode::AnyExpansion => word.word_nodes.expansion_selections(),
+            };
+
+            // The full form with placeholders, see `word_matches`
+            if word.indexed_full_word.is_some() {
+                selections.push(word.word_nodes.iter()
+                    .map(|node| matches!(*node, WordNode::Word(_) | WordNode::Round(_) | WordNode::Placeholder(_)))
+                    .collect());
+            }
+
+            // Highlight the first matching expansion only
+            for selection in selections {
+                let projection = Projection::new(word, &selection);
+                let terms = find_terms(&self.regex, &projection.text);
+
+                if !terms.is_empty() {
+                    spans.extend(terms.into_iter().flat_map(|term| projection.to_display_spans(term)));
+                    break;
+                }
+            }
+        }
+
+        let search_acronyms = matches!(self.query_field, QueryField::Acronyms | QueryField::All);
+        let search_comments = matches!(self.query_field, QueryField::Comments | QueryField::All);
+
+        for (node, offset) in word.word_nodes.iter().zip(word.word_nodes.display_offsets()) {
+            match *node {
+                // `<foo, bar>`
+                WordNode::Angle(ref acronyms) if search_acronyms => {
+                    let mut start = offset + 1;
+
+                    for acronym in acronyms {
+                        if self.keyword_regex.is_match(acronym) {
+                            spans.push(start..start + acronym.len());
+                        }
+
+                        start += acronym.len() + 2;
+                    }
+                }
+                // `[comment]`
+                WordNode::Square(ref comment) if search_comments => {
+                    let start = offset + 1;
+
+                    spans.extend(find_terms(&self.regex, comment).into_iter()
+                        .map(|term| start + term.start..start + term.end));
+                }
+                _ => {}
+            }
+        }
+
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+}
+
+/// The byte ranges of the matched terms, which are captured by the first group of `regex`.
+fn find_terms(regex: &Regex, text: &str) -> Vec<Range<usize>> {
+    regex.captures_iter(text)
+        .filter_map(|captures| captures.get(1))
+        .filter(|term| term.start() < term.end())
+        .map(|term| term.start()..term.end())
+        .collect()
+}
+
+/// The text of the selected words and optional parts of a `DictWord`, as searched by a `TermMatcher`,
+/// which keeps track of the position of every part in the displayed word.
+struct Projection {
+    text: String,
+    /// Start in `text`, start in the displayed word and length of every part
+    segments: Vec<(usize, usize, usize)>,
+}
+
+impl Projection {
+    /// `selection` contains for every node of the word, whether it is part of the projection.
+    fn new(word: &DictWord, selection: &[bool]) -> Self {
+        let mut parts = vec![];
+
+        for ((node, offset), &selected) in word.word_nodes.iter().zip(word.word_nodes.display_offsets()).zip(selection) {
+            match *node {
+                WordNode::Word(ref s) if selected => parts.push((s, offset)),
+                // Skip the opening bracket
+                WordNode::Round(ref s) if selected => parts.push((s, offset + 1)),
+                WordNode::Placeholder(ref s) if selected => parts.push((s, offset)),
+                _ => {}
+            }
+        }
+
+        let mut text = String::new();
+        let mut segments = vec![];
+
+        for (i, (s, display_start)) in parts.into_iter().enumerate() {
+            if i > 0 {
+                text.push(' ');
+            }
+
+            segments.push((text.len(), display_start, s.len()));
+            text.push_str(s);
+        }
+
+        Projection {
+            text,
+            segments,
+        }
+    }
+
+    /// Maps a byte range of the projection onto the displayed word.
+    ///
+    /// A range spanning several parts is split into one range per part.
+    fn to_display_spans(&self, span: Range<usize>) -> Vec<Range<usize>> {
+        self.segments.iter().filter_map(|&(start, display_start, len)| {
+            let from = max(span.start, start);
+            let to = min(span.end, start + len);
+
+            if from < to {
+                Some(display_start + from - start..display_start + to - start)
+            } else {
+                None
+            }
+        }).collect()
+    }
+}
+
+/// Splits a German definite article off the query term, e.g. `die Bank` -> (`die`, `Bank`).
+pub(crate) fn split_article(query_term: &str) -> Option<(&'static str, &str)> {
+    let query_term = query_term.trim();
+    let article_end = query_term.find(char::is_whitespace)?;
+
+    let article = match query_term[..article_end].to_lowercase().as_str() {
+        "der" => "der",
+        "die" => "die",
+        "das" => "das",
+        _ => return None,
+    };
+
+    let noun = query_term[article_end..].trim_start();
+
+    if noun.is_empty() {
+        None
+    } else {
+        Some((article, noun))
+    }
+}
+
+/// The German side of the dictionary, if it is searched by the query.
+fn german_side(query: &DictQuery) -> Option<EntrySide> {
+    let languages = query.dict.get_language_pair();
+
+    if languages.left_language == Language::DE && query.query_direction != QueryDirection::ToLeft {
+        Some(EntrySide::Left)
+    } else if languages.right_language == Language::DE && query.query_direction != QueryDirection::ToRight {
+        Some(EntrySide::Right)
+    } else {
+        None
+    }
+}
+
+/// Translates a glob pattern into an equivalent regex.
+///
+/// - `*` matches any number of characters
+/// - `?` matches a single character
+/// - `[abc]`, `[a-z]` match a single character of the class, `[!abc]` and `[^abc]` negate the class
+///
+/// Everything else is matched literally, including a `[` without a closing `]`.
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut regex = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '[' => {
+                let negated = matches!(chars.get(i + 1), Some(&'!') | Some(&'^'));
+                let class_start = if negated { i + 2 } else { i + 1 };
+
+                // The first character of a class may be a `]`
+                let class_end = chars.iter().skip(class_start + 1).position(|&c| c == ']')
+                    .map(|offset| class_start + 1 + offset);
+
+                match class_end {
+                    Some(class_end) => {
+                        regex.push('[');
+                        if negated {
+                            regex.push('^');
+                        }
+
+                        for (j, &c) in chars[class_start..class_end].iter().enumerate() {
+                            let is_range = c == '-' && j > 0 && class_start + j + 1 < class_end;
+
+                            if is_range {
+                                regex.push('-');
+                            } else {
+                                regex.push_str(&escape(&c.to_string()));
+                            }
+                        }
+
+                        regex.push(']');
+                        i = class_end;
+                    }
+                    None => regex.push_str(&escape("[")),
+                }
+            }
+            c => regex.push_str(&escape(&c.to_string())),
+        }
+
+        i += 1;
+    }
+
+    regex
+}
+
+/// The sides of an entry a `MatchNode` matched on.
+///
+/// Restrictions like `QueryNode::Class` match without matching a side.
+#[derive(Clone, Copy, Default, Debug)]
+struct MatchedSides {
+    left: bool,
+    right: bool,
+}
+
+impl MatchedSides {
+    fn union(self, other: MatchedSides) -> MatchedSides {
+        MatchedSides {
+            left: self.left || other.left,
+            right: self.right || other.right,
+        }
+    }
+
+    fn into_entry_side(self, query_direction: QueryDirection) -> EntrySide {
+        match (self.left, self.right, query_direction) {
+            (true, true, _) => EntrySide::Both,
+            (true, false, _) => EntrySide::Left,
+            (false, true, _) => EntrySide::Right,
+            (false, false, QueryDirection::ToRight) => EntrySide::Left,
+            (false, false, QueryDirection::ToLeft) => EntrySide::Right,
+            (false, false, QueryDirection::Bidirectional) => EntrySide::Both,
+        }
+    }
+}
+
+/// Compiled node of a query.
+#[derive(Debug)]
+enum MatchNode {
+    Term(TermMatcher),
+    Class(WordClass),
+    Gender(Gender),
+    /// A German noun with its definite article, e.g. `die Bank`, which only matches the German `side`
+    Article {
+        term: TermMatcher,
+        article: &'static str,
+        side: EntrySide,
+    },
+    Not(Box<MatchNode>),
+    And(Vec<MatchNode>),
+    Or(Vec<MatchNode>),
+}
+
+impl MatchNode {
+    fn compile(node: &QueryNode, query: &DictQuery) -> DictResult<Self> {
+        let term = |term: &str, query_field: QueryField| -> DictResult<MatchNode> {
+            Ok(MatchNode::Term(TermMatcher::new(term, QueryType::Boolean, query_field, query)?))
+        };
+
+        let compile_all = |nodes: &[QueryNode]| -> DictResult<Vec<MatchNode>> {
+            nodes.iter().map(|node| MatchNode::compile(node, query)).collect()
+        };
+
+        Ok(match *node {
+            QueryNode::Term(ref s) | QueryNode::Phrase(ref s) => term(s, query.query_field)?,
+            QueryNode::Acronym(ref s) => term(s, QueryField::Acronyms)?,
+            QueryNode::Comment(ref s) => term(s, QueryField::Comments)?,
+            QueryNode::Class(word_class) => MatchNode::Class(word_class),
+            QueryNode::Gender(gender) => MatchNode::Gender(gender),
+            QueryNode::Not(ref node) => MatchNode::Not(Box::new(MatchNode::compile(node, query)?)),
+            QueryNode::And(ref nodes) => MatchNode::And(compile_all(nodes)?),
+            QueryNode::Or(ref nodes) => MatchNode::Or(compile_all(nodes)?),
+        })
+    }
+
+    /// Collects the spans of all terms, which are not negated.
+    fn collect_spans(&self, entry: &DictEntry, query_direction: QueryDirection,
+                     left_spans: &mut Vec<Range<usize>>, right_spans: &mut Vec<Range<usize>>) {
+        match *self {
+            MatchNode::Term(ref term_matcher) => {
+                if query_direction != QueryDirection::ToLeft {
+                    left_spans.extend(term_matcher.word_spans(&entry.left_word));
+                }
+
+                if query_direction != QueryDirection::ToRight {
+                    right_spans.extend(term_matcher.word_spans(&entry.right_word));
+                }
+            }
+            MatchNode::Article { ref term, side, .. } => match side {
+                EntrySide::Right => right_spans.extend(term.word_spans(&entry.right_word)),
+                _ => left_spans.extend(term.word_spans(&entry.left_word)),
+            },
+            MatchNode::And(ref nodes) | MatchNode::Or(ref nodes) => for node in nodes {
+                node.collect_spans(entry, query_direction, left_spans, right_spans);
+            },
+            MatchNode::Class(_) | MatchNode::Gender(_) | MatchNode::Not(_) => {}
+        }
+    }
+
+    fn matches(&self, entry: &DictEntry, query_direction: QueryDirection) -> Option<MatchedSides> {
+        let matched = |is_match: bool| if is_match {
+            Some(MatchedSides::default())
+        } else {
+            None
+        };
+
+        match *self {
+            MatchNode::Term(ref term_matcher) => {
+                let sides = MatchedSides {
+                    left: query_direction != QueryDirection::ToLeft
+                        && term_matcher.word_matches(&entry.left_word),
+                    right: query_direction != QueryDirection::ToRight
+                        && term_matcher.word_matches(&entry.right_word),
+                };
+
+                if sides.left || sides.right {
+                    Some(sides)
+                } else {
+                    None
+                }
+            }
+            MatchNode::Class(word_class) => matched(entry.word_classes.contains(&word_class)),
+            MatchNode::Gender(gender) => matched(entry.left_word.has_gender(gender) || entry.right_word.has_gender(gender)),
+            MatchNode::Article { ref term, article, side } => {
+                let word = match side {
+                    EntrySide::Right => &entry.right_word,
+                    _ => &entry.left_word,
+                };
+
+                if word.articles.contains(&article) && term.word_matches(word) {
+                    Some(MatchedSides {
+                        left: side == EntrySide::Left,
+                        right: side == EntrySide::Right,
+                    })
+                } else {
+                    None
+                }
+            }
+            MatchNode::Not(ref node) => matched(node.matches(entry, query_direction).is_none()),
+            MatchNode::And(ref nodes) => nodes.iter().try_fold(MatchedSides::default(), |sides, node| {
+                node.matches(entry, query_direction).map(|node_sides| sides.union(node_sides))
+            }),
+            MatchNode::Or(ref nodes) => nodes.iter()
+                .filter_map(|node| node.matches(entry, query_direction))
+                .fold(None, |sides, node_sides| match sides {
+                    Some(sides) => Some(node_sides.union(sides)),
+                    None => Some(node_sides),
+                }),
+        }
+    }
+}
+
+/// Compiled matching logic of a `DictQuery`.
+#[derive(Debug)]
+pub(crate) struct QueryMatcher {
+    root: MatchNode,
+    filter: EntryFilter,
+    /// The query direction, which may be overridden by the query language
+    pub(crate) query_direction: QueryDirection,
+    /// The term used to compute the `Relevance` of the matching entries
+    pub(crate) relevance_term: String,
+}
+
+impl QueryMatcher {
+    pub(crate) fn new(query: &DictQuery) -> DictResult<Self> {
+        match query.query_type {
+            QueryType::Boolean => {
+                let expr = QueryExpr::parse(query.query_term)?;
+
+                let query_direction = match expr.language {
+                    Some(ref language) => query.dict.get_language_pair().infer_query_direction(language)?,
+                    None => query.query_direction,
+                };
+
+                Ok(QueryMatcher {
+                    root: MatchNode::compile(&expr.root, query)?,
+                    filter: query.filter.clone(),
+                    query_direction,
+                    relevance_term: expr.root.positive_terms().join(" "),
+                })
+            }
+            query_type => {
+                let term = MatchNode::Term(TermMatcher::new(query.query_term, query_type, query.query_field, query)?);
+
+                let article_query = match query_type {
+                    QueryType::Word | QueryType::Exact | QueryType::Phonetic => split_article(query.query_term),
+                    QueryType::Regex | QueryType::Wildcard | QueryType::Boolean => None,
+                };
+
+                match (article_query, german_side(query)) {
+                    (Some((article, noun)), Some(side)) => Ok(QueryMatcher {
+                        // The article may also be part of the word, e.g. `die Hard`
+                        root: MatchNode::Or(vec![term, MatchNode::Article {
+                            term: TermMatcher::new(noun, query_type, query.query_field, query)?,
+                            article,
+                            side,
+                        }]),
+                        filter: query.filter.clone(),
+                        query_direction: query.query_direction,
+                        relevance_term: noun.to_string(),
+                    }),
+                    _ => Ok(QueryMatcher {
+                        root: term,
+                        filter: query.filter.clone(),
+                        query_direction: query.query_direction,
+                        relevance_term: query.query_term.to_string(),
+                    }),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn matched_side(&self, entry: &DictEntry) -> Option<EntrySide> {
+        if !self.filter.matches(entry) {
+            return None;
+        }
+
+        self.root.matches(entry, self.query_direction)
+            .map(|sides| sides.into_entry_side(self.query_direction))
+    }
+
+    /// Where the query matched an entry, which was accepted by `matched_side`.
+    pub(crate) fn entry_match(&self, entry: &DictEntry, side: EntrySide) -> EntryMatch {
+        let mut left_spans = vec![];
+        let mut right_spans = vec![];
+
+        self.root.collect_spans(entry, self.query_direction, &mut left_spans, &mut right_spans);
+
+        left_spans.sort_by_key(|span| span.start);
+        right_spans.sort_by_key(|span| span.start);
+
+        EntryMatch {
+            side,
+            left_spans,
+            right_spans,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_to_regex() {
+        let data = vec![
+            ("Haus*", "Haus.*"),
+            ("?aus", ".aus"),
+            ("[abc]", "[abc]"),
+            ("[a-z]x", "[a-z]x"),
+            ("[!ab]", "[^ab]"),
+            ("[^ab]", "[^ab]"),
+            ("[]a]", r"[\]a]"),
+            ("[-a]", r"[\-a]"),
+            ("[ab", r"\[ab"),
+            ("a.b(c)", r"a\.b\(c\)"),
+        ];
+
+        for (glob, expected) in data {
+            assert_eq!(expected, glob_to_regex(glob), "glob: {:?}", glob);
+        }
+    }
+
+    #[test]
+    fn test_glob_regex_matches() {
+        let matches = |glob: &str, text: &str| {
+            Regex::new(&format!("^{}$", glob_to_regex(glob))).unwrap().is_match(text)
+        };
+
+        assert!(matches("Haus*", "Haustür"));
+        assert!(matches("?aus", "Maus"));
+        assert!(!matches("?aus", "Haus "));
+        assert!(matches("[HM]aus", "Maus"));
+        assert!(!matches("[!HM]aus", "Maus"));
+        assert!(matches("*", ""));
+    }
+
+    #[test]
+    fn test_word_spans() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+
+        let spans = |term: &str, query_field: QueryField, word: &str| {
+            let word_nodes = WordNodes::try_from(word).unwrap();
+            let word = DictWord::try_from((&word_nodes).into()).unwrap();
+
+            let mut query = dict.query(term);
+            query.set_field(query_field);
+
+            let term_matcher = TermMatcher::new(term, QueryType::Word, query_field, &query).unwrap();
+
+            term_matcher.word_spans(&word).into_iter()
+                .map(|span| word.to_string()[span].to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec!["Haus"], spans("haus", QueryField::IndexedWord, "das Haus {n}"));
+        assert_eq!(vec!["kleine", "Haus"], spans("kleine haus", QueryField::IndexedWord, "(kleine) Haus"));
+        assert_eq!(vec!["ABC"], spans("abc", QueryField::All, "foo <XYZ, ABC>"));
+        assert_eq!(vec!["bar"], spans("bar", QueryField::Comments, "foo [bar baz]"));
+        assert!(spans("bar", QueryField::IndexedWord, "foo [bar]").is_empty());
+    }
+
+    #[test]
+    fn test_romanized_case_sensitive() {
+        let dict = Dict::create("tests/database/test_database_transliterate.txt").unwrap();
+        let matches = |term: &str, case_sensitive: bool| {
+            let mut query = dict.query(term);
+            query.case_sensitive(case_sensitive);
+            query.execute().unwrap().entries().len()
+        };
+
+        assert_eq!(1, matches("koshka", false));
+        assert_eq!(1, matches("MOSKVA", false));
+
+        assert_eq!(1, matches("koshka", true));
+        assert_eq!(0, matches("Koshka", true));
+        assert_eq!(1, matches("Moskva", true));
+        assert_eq!(0, matches("moskva", true));
+    }
+
+    #[test]
+    fn test_split_article() {
+        assert_eq!(Some(("die", "Bank")), split_article("die Bank"));
+        assert_eq!(Some(("der", "alte Hund")), split_article("Der  alte Hund"));
+        assert_eq!(None, split_article("die"));
+        assert_eq!(None, split_article("dies Haus"));
+    }
+}
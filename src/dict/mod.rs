@@ -1,39 +1,163 @@
 extern crate csv;
 
+use dict::filter::{EntryFilter, WordClassFilter};
 use dict::grouped::DictQueryResultGrouped;
+use dict::matcher::QueryMatcher;
+use dict::phonetic::PhoneticAlgorithm;
+use dict::placeholder::Placeholder;
+use dict::region::Region;
+use dict::register::Register;
+use dict::relevance::{Relevance, RelevanceModel};
+use dict::stats::DictLoadStats;
+use dict::subject::Subject;
+use dict::suggest::Suggestion;
 use error::{DictError, DictResult};
 use failure::Backtrace;
 use parse::html::HtmlDecodedDictEntry;
-use parse::raw_csv::{get_csv_reader_from_path, incomplete_records_filter, RawDictEntry};
-use parse::word_ast::{WordNodes, WordNodesDictEntry};
+use parse::raw_csv::{get_csv_reader_from_path, incomplete_records_filter, too_many_columns_filter, RawDictEntry};
+use parse::word_ast::{WordNode, WordNodes, WordNodesDictEntry};
 use regex::{Captures, escape, Regex, RegexBuilder};
+use std::borrow::Borrow;
+use std::cell::Cell;
 use std::fmt::{self, Display, Formatter};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::ops::Range;
 use std::path::Path;
+use std::slice;
+use std::iter::{Enumerate, Zip};
 use std::str::FromStr;
 
+mod batch;
+pub mod cache;
+pub mod filter;
+pub mod graph;
 pub mod grouped;
+mod matcher;
+mod normalize;
+mod phonetic;
+pub mod placeholder;
+pub mod relevance;
+mod random;
+pub mod region;
+pub mod register;
+pub mod stats;
+pub mod subject;
+pub mod suggest;
+pub mod synonyms;
+pub mod transliterate;
 
 /// Result of a translation query
+///
+/// The entries are borrowed from the `Dict` (`DictQueryResult<&DictEntry>`),
+/// use `to_owned_result` to get a result which owns its entries (`DictQueryResult<DictEntry>`).
 #[derive(Clone, Eq, PartialEq, Debug)]
-pub struct DictQueryResult {
-    entries: Vec<DictEntry>,
+pub struct DictQueryResult<E: Borrow<DictEntry>> {
+    entries: Vec<E>,
+    relevances: Vec<Relevance>,
+    matches: Vec<EntryMatch>,
     query_direction: QueryDirection,
 }
 
-impl DictQueryResult {
+impl<E: Borrow<DictEntry>> DictQueryResult<E> {
     /// Returns a slice of all entries found in the query.
-    pub fn entries(&self) -> &[DictEntry] {
+    pub fn entries(&self) -> &[E] {
         &self.entries
     }
 
+    /// Returns the relevance of every entry, in the same order as `entries`.
+    pub fn relevances(&self) -> &[Relevance] {
+        &self.relevances
+    }
+
+    /// Returns where the query matched every entry, in the same order as `entries`.
+    pub fn matches(&self) -> &[EntryMatch] {
+        &self.matches
+    }
+
+    /// Returns an iterator over all entries paired with their relevance.
+    pub fn entries_with_relevance<'a>(&'a self) -> Zip<slice::Iter<'a, E>, slice::Iter<'a, Relevance>> {
+        self.entries.iter().zip(self.relevances.iter())
+    }
+
+    /// Sorts the entries by descending relevance.
+    ///
+    /// Entries with the same relevance keep their order in the database file.
+    pub fn sorted_by_relevance(self) -> Self {
+        let mut scored: Vec<_> = self.entries.into_iter()
+            .zip(self.relevances)
+            .zip(self.matches)
+            .collect();
+
+        scored.sort_by(|&((_, ref left), _), &((_, ref right), _)| right.cmp(left));
+
+        let (scored_entries, matches): (Vec<_>, _) = scored.into_iter().unzip();
+        let (entries, relevances) = scored_entries.into_iter().unzip();
+
+        DictQueryResult {
+            entries,
+            relevances,
+            matches,
+            query_direction: self.query_direction,
+        }
+    }
+
+    /// Skips the first `offset` entries and keeps at most `limit` entries.
+    fn paged(self, offset: usize, limit: Option<usize>) -> Self {
+        let limit = limit.unwrap_or(usize::MAX);
+
+        DictQueryResult {
+            entries: self.entries.into_iter().skip(offset).take(limit).collect(),
+            relevances: self.relevances.into_iter().skip(offset).take(limit).collect(),
+            matches: self.matches.into_iter().skip(offset).take(limit).collect(),
+            query_direction: self.query_direction,
+        }
+    }
+
+    /// Clones the entries, so that the result no longer borrows the `Dict`.
+    pub fn to_owned_result(&self) -> DictQueryResult<DictEntry> {
+        DictQueryResult {
+            entries: self.entries.iter().map(|entry| entry.borrow().clone()).collect(),
+            relevances: self.relevances.clone(),
+            matches: self.matches.clone(),
+            query_direction: self.query_direction,
+        }
+    }
+
     /// Converts a `DictQueryResult` into a grouped representation used for structured display of the found entries.
-    pub fn into_grouped(self) -> DictQueryResultGrouped {
+    pub fn into_grouped(self) -> DictQueryResultGrouped<E> {
         DictQueryResultGrouped::from(self)
     }
 }
 
+/// Where a query matched an entry of a `DictQueryResult`.
+///
+/// The spans are byte ranges of the matched text within the displayed word (`DictWord::to_string`).
+/// They can be used to highlight the match.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EntryMatch {
+    side: EntrySide,
+    left_spans: Vec<Range<usize>>,
+    right_spans: Vec<Range<usize>>,
+}
+
+impl EntryMatch {
+    /// The side of the entry, which matched the query.
+    pub fn side(&self) -> EntrySide {
+        self.side
+    }
+
+    /// The matched byte ranges within the displayed left word.
+    pub fn left_spans(&self) -> &[Range<usize>] {
+        &self.left_spans
+    }
+
+    /// The matched byte ranges within the displayed right word.
+    pub fn right_spans(&self) -> &[Range<usize>] {
+        &self.right_spans
+    }
+}
+
 /// Structure that contains all dictionary entries
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Dict {
@@ -42,6 +166,9 @@ pub struct Dict {
 
     // Languages
     languages: DictLanguagePair,
+
+    /// Problems found while reading the database
+    load_stats: DictLoadStats,
 }
 
 impl Dict {
@@ -53,27 +180,66 @@ impl Dict {
 
         let mut reader = get_csv_reader_from_path(&path)?;
         let languages = DictLanguagePair::from_path(&path)?;
+        let mut load_stats = DictLoadStats::default();
+        let incomplete_records = Cell::new(0);
+        let too_many_columns = Cell::new(0);
+
         let records = reader
-            .deserialize()
-            .filter(incomplete_records_filter);
+            .records()
+            .filter(|record| {
+                let is_complete = incomplete_records_filter(record);
+
+                if !is_complete {
+                    incomplete_records.set(incomplete_records.get() + 1);
+                }
+
+                is_complete
+            })
+            .filter(|record| {
+                let has_valid_len = too_many_columns_filter(record);
+
+                if !has_valid_len {
+                    too_many_columns.set(too_many_columns.get() + 1);
+                }
+
+                has_valid_len
+            });
 
         let mut entries = vec![];
 
         for record in records {
-            let raw_entry: RawDictEntry = record?;
+            let record = record?;
+
+            let raw_entry: RawDictEntry = record.deserialize(None)?;
             trace!("raw_entry = {:#?}", raw_entry);
             let html_decoded_entry = HtmlDecodedDictEntry::from(&raw_entry);
             trace!("html_decoded_entry = {:#?}", html_decoded_entry);
             let word_ast = WordNodesDictEntry::from(&html_decoded_entry);
             trace!("word_ast = {:#?}", word_ast);
-            if let Ok(entry) = DictEntry::try_from(word_ast) {
-                trace!("entry = {:#?}", entry);
-                entries.push(entry);
+            load_stats.fallback_words += word_ast.fallback_words as usize;
+            match DictEntry::try_from(word_ast) {
+                Ok(entry) => {
+                    trace!("entry = {:#?}", entry);
+                    entries.push(entry);
+                }
+                Err(err) => {
+                    info!("Drop invalid entry: {}", err);
+                    load_stats.invalid_entries += 1;
+                }
             };
         }
+        load_stats.incomplete_records = incomplete_records.get();
+        load_stats.too_many_columns = too_many_columns.get();
+
+        for entry in &mut entries {
+            entry.left_word.index_language(&languages.left_language);
+            entry.right_word.index_language(&languages.right_language);
+        }
+
         Ok(Self {
             entries,
             languages,
+            load_stats,
         })
     }
 
@@ -104,6 +270,14 @@ impl Dict {
             query_term,
             query_type: QueryType::Word,
             query_direction: QueryDirection::Bidirectional,
+            query_field: QueryField::IndexedWord,
+            case_sensitive: false,
+            match_mode: MatchMode::WithOptionalParts,
+            transliterate: true,
+            relevance_model: RelevanceModel::default(),
+            filter: EntryFilter::default(),
+            offset: 0,
+            limit: None,
         }
     }
 }
@@ -116,6 +290,14 @@ pub struct DictQuery<'a, 'b> {
     query_term: &'b str,
     query_type: QueryType,
     query_direction: QueryDirection,
+    query_field: QueryField,
+    case_sensitive: bool,
+    match_mode: MatchMode,
+    transliterate: bool,
+    relevance_model: RelevanceModel,
+    filter: EntryFilter,
+    offset: usize,
+    limit: Option<usize>,
 }
 
 impl<'a, 'b> DictQuery<'a, 'b> {
@@ -131,6 +313,127 @@ impl<'a, 'b> DictQuery<'a, 'b> {
         self
     }
 
+    /// Set the field of the words, which is searched.
+    pub fn set_field(&mut self, query_field: QueryField) -> &mut Self {
+        self.query_field = query_field;
+        self
+    }
+
+    /// Set whether the query is case sensitive (default: `false`).
+    ///
+    /// Case sensitive queries distinguish e.g. the noun "Essen" from the verb "essen".
+    pub fn case_sensitive(&mut self, case_sensitive: bool) -> &mut Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Set how optional parts of the words are treated by the query.
+    pub fn set_match_mode(&mut self, match_mode: MatchMode) -> &mut Self {
+        self.match_mode = match_mode;
+        self
+    }
+
+    /// Set whether a Latin-script query also matches the romanized form of
+    /// Cyrillic and Greek words (default: `true`), e.g. "koshka" matches "кошка".
+    ///
+    /// See `transliterate::romanize` for the supported languages.
+    pub fn transliterate(&mut self, transliterate: bool) -> &mut Self {
+        self.transliterate = transliterate;
+        self
+    }
+
+    /// Set the model used to rank the results by relevance.
+    pub fn set_relevance_model(&mut self, relevance_model: RelevanceModel) -> &mut Self {
+        self.relevance_model = relevance_model;
+        self
+    }
+
+    /// Set the filter, which restricts the matching entries.
+    pub fn set_filter(&mut self, filter: EntryFilter) -> &mut Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Only match entries with at least one of the given word classes.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn any_word_class(&mut self, word_classes: &[WordClass]) -> &mut Self {
+        self.filter.word_classes = Some(WordClassFilter::Any(word_classes.to_vec()));
+        self
+    }
+
+    /// Only match entries with all of the given word classes.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn all_word_classes(&mut self, word_classes: &[WordClass]) -> &mut Self {
+        self.filter.word_classes = Some(WordClassFilter::All(word_classes.to_vec()));
+        self
+    }
+
+    /// Only match entries with at least one of the given subjects, e.g. only medical terms.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn any_subject(&mut self, subjects: &[Subject]) -> &mut Self {
+        self.filter.subjects = subjects.to_vec();
+        self
+    }
+
+    /// Only match entries, whose words may be used in `region`, e.g. no `[Am.]` words for `Region::British`.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn region(&mut self, region: Region) -> &mut Self {
+        self.filter.region = Some(region);
+        self
+    }
+
+    /// Rank entries of `region` higher and entries of other regions of the same language lower.
+    ///
+    /// Convenience function for `set_relevance_model`
+    pub fn prefer_region(&mut self, region: Region) -> &mut Self {
+        self.relevance_model.preferred_region = Some(region);
+        self
+    }
+
+    /// Only match entries with the given gender tag.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn gender(&mut self, gender: Gender) -> &mut Self {
+        self.filter.gender = Some(gender);
+        self
+    }
+
+    /// Exclude entries with vulgar or pejorative words, see `Register::is_offensive`.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn family_safe(&mut self) -> &mut Self {
+        self.filter.family_safe = true;
+        self
+    }
+
+    /// Only match entries with any gender tag.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn any_gender(&mut self) -> &mut Self {
+        self.filter.any_gender = true;
+        self
+    }
+
+    /// Only match entries, whose word on `side` has at least `min` words.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn min_word_count(&mut self, side: EntrySide, min: u8) -> &mut Self {
+        self.filter.set_min_word_count(side, min);
+        self
+    }
+
+    /// Only match entries, whose word on `side` has at most `max` words.
+    ///
+    /// Convenience function for `set_filter`
+    pub fn max_word_count(&mut self, side: EntrySide, max: u8) -> &mut Self {
+        self.filter.set_max_word_count(side, max);
+        self
+    }
+
     /// Set the query term.
     pub fn set_term<'c>(self, query_term: &'c str) -> DictQuery<'a, 'c> {
         DictQuery {
@@ -138,6 +441,14 @@ impl<'a, 'b> DictQuery<'a, 'b> {
             query_term,
             query_type: self.query_type,
             query_direction: self.query_direction,
+            query_field: self.query_field,
+            case_sensitive: self.case_sensitive,
+            match_mode: self.match_mode,
+            transliterate: self.transliterate,
+            relevance_model: self.relevance_model,
+            filter: self.filter,
+            offset: self.offset,
+            limit: self.limit,
         }
     }
 
@@ -174,37 +485,201 @@ impl<'a, 'b> DictQuery<'a, 'b> {
         self
     }
 
+    /// Search for a glob pattern, e.g. `Haus*`, `?aus` or `[HM]aus`
+    ///
+    /// Convenience function for `set_query_type`
+    pub fn wildcard(&mut self) -> &mut Self {
+        self.set_type(QueryType::Wildcard);
+        self
+    }
+
+    /// Search using the boolean query language, see `query_ast`.
+    ///
+    /// Convenience function for `set_query_type`
+    pub fn boolean(&mut self) -> &mut Self {
+        self.set_type(QueryType::Boolean);
+        self
+    }
+
+    /// Search for words, which sound like the query, e.g. `nite` finds `night`.
+    ///
+    /// Uses the Kölner Phonetik for German, Double Metaphone for English
+    /// and a simple phonetic code for other languages.
+    /// Cyrillic and Greek words are compared using their romanized form.
+    ///
+    /// Convenience function for `set_query_type`
+    pub fn phonetic(&mut self) -> &mut Self {
+        self.set_type(QueryType::Phonetic);
+        self
+    }
+
+    /// Skip the first `offset` matching entries.
+    ///
+    /// `execute` skips the most relevant entries, `iter` the first entries in the order of the database file.
+    pub fn offset(&mut self, offset: usize) -> &mut Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Return at most `limit` matching entries.
+    ///
+    /// `execute` returns the most relevant entries, `iter` the first entries in the order of the database file.
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
     /// Execute the query.
-    pub fn execute(&self) -> DictResult<DictQueryResult> {
-        let regexp = match self.query_type {
-            QueryType::Word => RegexBuilder::new(&format!(r"(^|\s|-){}($|\s|-)", escape(self.query_term))).case_insensitive(true).build()?,
-            QueryType::Exact => RegexBuilder::new(&format!(r"^{}$", escape(self.query_term))).case_insensitive(true).build()?,
-            QueryType::Regex => RegexBuilder::new(&format!(r"^{}$", self.query_term)).case_insensitive(true).build()?,
+    ///
+    /// The entries are in the order of the database file, use `DictQueryResult::sorted_by_relevance` to rank them.
+    /// If an offset or a limit is set, all matching entries are ranked first
+    /// and the result contains the requested page of the entries sorted by relevance.
+    pub fn execute(&self) -> DictResult<DictQueryResult<&'a DictEntry>> {
+        Ok(self.rank(&self.find_matches()?))
+    }
+
+    /// Finds the matching entries, without ranking them.
+    ///
+    /// Ignores the offset and the limit, which are applied after ranking.
+    fn find_matches(&self) -> DictResult<QueryMatches> {
+        let mut iter = DictQueryIter {
+            matcher: QueryMatcher::new(self)?,
+            entries: self.dict.entries.iter().enumerate(),
+            offset: 0,
+            limit: None,
         };
 
-        Ok(DictQueryResult {
-            entries: self.dict.entries.iter().filter(|entry| {
-                match self.query_direction {
-                    QueryDirection::ToRight => regexp.is_match(&entry.left_word.indexed_word),
-                    QueryDirection::ToLeft => regexp.is_match(&entry.right_word.indexed_word),
-                    QueryDirection::Bidirectional => regexp.is_match(&entry.left_word.indexed_word)
-                        || regexp.is_match(&entry.right_word.indexed_word),
-                }
-            }).cloned().collect(),
-            query_direction: self.query_direction,
+        let mut entries = vec![];
+        let mut matches = vec![];
+
+        while let Some((index, entry, matched_side)) = iter.next_match() {
+            matches.push(iter.matcher.entry_match(entry, matched_side));
+            entries.push(index);
+        }
+
+        Ok(QueryMatches {
+            entries,
+            matches,
+            relevance_term: iter.matcher.relevance_term,
+            query_direction: iter.matcher.query_direction,
         })
     }
+
+    /// Computes the relevance of the matching entries and applies the offset and the limit.
+    fn rank(&self, query_matches: &QueryMatches) -> DictQueryResult<&'a DictEntry> {
+        let entries: Vec<&'a DictEntry> = query_matches.entries.iter()
+            .map(|&index| &self.dict.entries[index])
+            .collect();
+
+        let relevances = entries.iter().zip(&query_matches.matches)
+            .map(|(entry, entry_match)| self.relevance_model.score(entry, entry_match.side, &query_matches.relevance_term))
+            .collect();
+
+        let result = DictQueryResult {
+            entries,
+            relevances,
+            matches: query_matches.matches.clone(),
+            query_direction: query_matches.query_direction,
+        };
+
+        if self.offset == 0 && self.limit.is_none() {
+            result
+        } else {
+            result.sorted_by_relevance().paged(self.offset, self.limit)
+        }
+    }
+
+    /// Proposes at most `max_suggestions` headwords similar to the query term,
+    /// e.g. if the query found no entries.
+    ///
+    /// Convenience function for `Dict::suggest`
+    pub fn suggestions(&self, max_suggestions: usize) -> Vec<Suggestion> {
+        self.dict.suggest(self.query_term, self.query_direction, max_suggestions)
+    }
+
+    /// Returns a lazy iterator over the matching entries.
+    ///
+    /// In contrast to `execute`, the entries are not ranked,
+    /// so the caller can page through the results or stop early.
+    /// The offset and the limit are applied in the order of the database file.
+    pub fn iter(&self) -> DictResult<DictQueryIter<'a>> {
+        Ok(DictQueryIter {
+            matcher: QueryMatcher::new(self)?,
+            entries: self.dict.entries.iter().enumerate(),
+            offset: self.offset,
+            limit: self.limit,
+        })
+    }
+}
+
+/// The entries matching a `DictQuery`, before ranking.
+#[derive(Clone, Debug)]
+struct QueryMatches {
+    /// The indices of the matching entries in the `Dict`
+    entries: Vec<usize>,
+    matches: Vec<EntryMatch>,
+    relevance_term: String,
+    query_direction: QueryDirection,
+}
+
+/// Lazy iterator over the entries matching a `DictQuery`. Created by `DictQuery::iter`.
+#[derive(Debug)]
+pub struct DictQueryIter<'a> {
+    matcher: QueryMatcher,
+    entries: Enumerate<slice::Iter<'a, DictEntry>>,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl<'a> DictQueryIter<'a> {
+    /// Returns the next matching entry, its index in the `Dict` and the matched side.
+    fn next_match(&mut self) -> Option<(usize, &'a DictEntry, EntrySide)> {
+        if self.limit == Some(0) {
+            return None;
+        }
+
+        for (index, entry) in &mut self.entries {
+            if let Some(matched_side) = self.matcher.matched_side(entry) {
+                if self.offset > 0 {
+                    self.offset -= 1;
+                    continue;
+                }
+
+                if let Some(ref mut limit) = self.limit {
+                    *limit -= 1;
+                }
+
+                return Some((index, entry, matched_side));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> Iterator for DictQueryIter<'a> {
+    type Item = &'a DictEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_match().map(|(_, entry, _)| entry)
+    }
 }
 
 /// Different types of queries. Used by `DictQuery`.
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub enum QueryType {
     /// Search for exact matches
     Exact,
-    /// Every entry that contains the query-word is a hit
-    Regex,
     /// Search for regex, so the user can specify by himself what he wants to match
+    Regex,
+    /// Every entry that contains the query-word is a hit
     Word,
+    /// Search for a glob pattern with `*`, `?` and `[abc]`, e.g. `Haus*`
+    Wildcard,
+    /// Search using the boolean query language, see `query_ast`
+    Boolean,
+    /// Search for words, which sound like the query, e.g. `filosofie` finds `Philosophie`, see `DictQuery::phonetic`
+    Phonetic,
 }
 
 impl FromStr for QueryType {
@@ -217,6 +692,9 @@ impl FromStr for QueryType {
             "e" | "exact" => Exact,
             "r" | "regex" => Regex,
             "w" | "word" => Word,
+            "g" | "glob" | "wildcard" => Wildcard,
+            "b" | "bool" | "boolean" => Boolean,
+            "p" | "phonetic" => Phonetic,
             unknown => Err(DictError::UnknownQueryType {
                 query_type: unknown.to_string(),
                 backtrace: Backtrace::new(),
@@ -225,9 +703,56 @@ impl FromStr for QueryType {
     }
 }
 
+/// Which field of the words is searched by a query. Used by `DictQuery`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum QueryField {
+    /// The word including its optional parts (default).
+    IndexedWord,
+    /// The acronyms, e.g. `<USA>`. Acronyms are keywords, so they must match completely.
+    Acronyms,
+    /// The comments, e.g. `[Kommentar]`.
+    Comments,
+    /// All of the above.
+    All,
+}
+
+impl FromStr for QueryField {
+    type Err = DictError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::QueryField::*;
+
+        Ok(match s.to_lowercase().as_str() {
+            "w" | "word" => IndexedWord,
+            "a" | "acronym" | "acronyms" => Acronyms,
+            "c" | "comment" | "comments" => Comments,
+            "all" => All,
+            unknown => Err(DictError::UnknownQueryField {
+                query_field: unknown.to_string(),
+                backtrace: Backtrace::new(),
+            })?
+        })
+    }
+}
+
+/// How the optional parts of a word, e.g. `(optionales) Wort`, are treated by a query. Used by `DictQuery`.
+///
+/// Only affects `QueryField::IndexedWord`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum MatchMode {
+    /// Search the word including its optional parts: `optionales Wort` (default).
+    WithOptionalParts,
+    /// Search the word without its optional parts: `Wort`.
+    PlainWord,
+    /// Search every variant of the word with and without each optional part: `Wort`, `optionales Wort`.
+    ///
+    /// An exact query for "Wort" finds `(optionales) Wort`, like the website of dict.cc.
+    AnyExpansion,
+}
+
 /// In which direction a query is executed. Used by `DictQuery`.
 /// Can be inferred by `DictLanguagePair::infer_query_direction`.
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub enum QueryDirection {
     /// Search in the left language, to get results in the right language.
     ToRight,
@@ -237,6 +762,17 @@ pub enum QueryDirection {
     Bidirectional,
 }
 
+/// Side of a `DictEntry`, e.g. on which side a query matched.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum EntrySide {
+    /// The left word.
+    Left,
+    /// The right word.
+    Right,
+    /// Both words.
+    Both,
+}
+
 /// Structure that holds the word pair and it's class
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct DictEntry {
@@ -246,6 +782,8 @@ pub struct DictEntry {
     pub right_word: DictWord,
     /// List of word classes (`noun`, `verb`, `adj`, etc.).
     pub word_classes: Vec<WordClass>,
+    /// Subject areas of the subject column and of the comments (`[med.]`, `[tech.]`, etc.).
+    pub subjects: Vec<Subject>,
 }
 
 impl DictEntry {
@@ -255,13 +793,40 @@ impl DictEntry {
         for class in word_nodes_dict_entry.word_classes.split_whitespace() {
             classes.push(WordClass::try_from(class)?);
         }
+        let left_word = DictWord::try_from(word_nodes_dict_entry.left_word_nodes)?;
+        let right_word = DictWord::try_from(word_nodes_dict_entry.right_word_nodes)?;
+
+        let mut subjects = Subject::parse_column(&word_nodes_dict_entry.subjects);
+
+        for comment in left_word.comments().iter().chain(&right_word.comments()) {
+            for subject in Subject::parse_comment(comment) {
+                if !subjects.contains(&subject) {
+                    subjects.push(subject);
+                }
+            }
+        }
+
         Ok(DictEntry {
-            left_word: DictWord::try_from(word_nodes_dict_entry.left_word_nodes)?,
-            right_word: DictWord::try_from(word_nodes_dict_entry.right_word_nodes)?,
+            left_word,
+            right_word,
             word_classes: classes,
+            subjects,
         })
     }
 
+    /// The registers of both words, e.g. `[Colloquial]` for `Karre {f} [ugs.]` - `jalopy`.
+    pub fn registers(&self) -> Vec<Register> {
+        let mut registers = self.left_word.registers.clone();
+
+        for &register in &self.right_word.registers {
+            if !registers.contains(&register) {
+                registers.push(register);
+            }
+        }
+
+        registers
+    }
+
     fn get_max_word_count(&self) -> u8 {
         use std::cmp::max;
 
@@ -282,6 +847,78 @@ pub struct DictWord {
     /// `(a) Foo` -> `a foo`
     indexed_word: String,
 
+    /// The word with optional parts and placeholders in lowercase, if the word has placeholders.
+    /// Is used for searching the full form of the word.
+    ///
+    ///  Syntax:
+    /// `jdn. (oft) anrufen` -> `jdn. oft anrufen`
+    indexed_full_word: Option<String>,
+
+    /// The acronyms in lowercase. Are used as keywords for searching.
+    ///
+    ///  Syntax:
+    /// `<Foo, BAR>` -> `["foo", "bar"]`
+    indexed_acronyms: Vec<String>,
+
+    /// The comments in lowercase. Are used for searching.
+    ///
+    ///  Syntax:
+    /// `[Foo]` -> `["foo"]`
+    indexed_comments: Vec<String>,
+
+    /// The romanized indexed word in lowercase and without diacritics,
+    /// if the language of the word has a transliteration table.
+    /// Is used for searching with Latin-script queries.
+    ///
+    ///  Syntax:
+    /// `(за) кошка` -> `za koshka`
+    indexed_romanized: Option<String>,
+
+    /// The romanized word without brackets and without diacritics, but in its original case,
+    /// if the language of the word has a transliteration table.
+    /// Is used for case sensitive searching with Latin-script queries.
+    ///
+    ///  Syntax:
+    /// `(в) Москве` -> `v Moskve`
+    unbracketed_romanized: Option<String>,
+
+    /// The romanized word with optional parts, if the language of the word has a transliteration table.
+    romanized: Option<String>,
+
+    /// The phonetic codes of every token of the indexed word (or of the romanized word).
+    /// Are used for `QueryType::Phonetic`.
+    ///
+    ///  Syntax:
+    /// `(a) night` -> `[["A"], ["NT"]]`
+    indexed_phonetic: Vec<Vec<String>>,
+
+    /// The phonetic algorithm of the language of the word.
+    phonetic_algorithm: PhoneticAlgorithm,
+
+    /// The German definite articles derived from the genders, if the word is German.
+    ///
+    ///  Syntax:
+    /// `Wohnung {f}` -> `["die"]`
+    articles: Vec<&'static str>,
+
+    /// The regional varieties of the word, read from its comments.
+    ///
+    ///  Syntax:
+    /// `lorry [Br.]` -> `[British]`
+    regions: Vec<Region>,
+
+    /// The registers and style labels of the word, read from its comments.
+    ///
+    ///  Syntax:
+    /// `Karre {f} [ugs.]` -> `[Colloquial]`
+    registers: Vec<Register>,
+
+    /// The expansions of the placeholders in the same order as the placeholders.
+    ///
+    ///  Syntax:
+    /// `jdm. etw. geben` -> `["jemandem", "etwas"]`
+    placeholder_expansions: Vec<String>,
+
     /// The AST (abstract syntax tree) of the complete word.
     pub word_nodes: WordNodes<String>,
 
@@ -298,24 +935,186 @@ impl Display for DictWord {
 impl DictWord {
     /// Try to convert from a WordNode into a DictWord
     fn try_from(word_nodes: WordNodes<String>) -> DictResult<Self> {
+        let comments = word_nodes.build_comments();
+        let mut regions: Vec<Region> = vec![];
+        let mut registers: Vec<Register> = vec![];
+
+        for region in comments.iter().flat_map(|comment| Region::parse_comment(comment)) {
+            if !regions.contains(&region) {
+                regions.push(region);
+            }
+        }
+
+        for register in comments.iter().flat_map(|comment| Register::parse_comment(comment)) {
+            if !registers.contains(&register) {
+                registers.push(register);
+            }
+        }
+
         Ok(DictWord {
             indexed_word: word_nodes.build_indexed_word(),
+            indexed_full_word: None,
+            indexed_acronyms: word_nodes.build_acronyms().iter().map(|acronym| acronym.to_lowercase()).collect(),
+            indexed_comments: comments.iter().map(|comment| comment.to_lowercase()).collect(),
+            indexed_romanized: None,
+            unbracketed_romanized: None,
+            romanized: None,
+            indexed_phonetic: vec![],
+            phonetic_algorithm: PhoneticAlgorithm::Simple,
+            articles: vec![],
+            regions,
+            registers,
+            placeholder_expansions: vec![],
             word_count: word_nodes.count_words(),
             word_nodes,
         })
     }
 
-    fn to_colored_string(&self) -> String {
-        self.word_nodes.to_colored_string()
+    fn to_highlighted_string(&self, spans: &[Range<usize>]) -> String {
+        self.word_nodes.to_highlighted_string(spans)
+    }
+
+    fn to_highlighted_expanded_string(&self, spans: &[Range<usize>]) -> String {
+        self.word_nodes.to_highlighted_expanded_string(spans, &self.placeholder_expansions)
+    }
+
+    /// Computes the language dependent fields of the word, which is written in `language`.
+    fn index_language(&mut self, language: &Language) {
+        // Placeholders are excluded from the indexed word and the word count, but kept in the indexed full word
+        self.word_nodes.mark_placeholders(|position, word| Placeholder::parse_word(word, position, language).is_some());
+        self.indexed_word = self.word_nodes.build_indexed_word();
+        self.word_count = self.word_nodes.count_words();
+
+        let mut position = 0;
+        let mut placeholder_expansions = vec![];
+
+        for node in self.word_nodes.iter() {
+            match *node {
+                WordNode::Placeholder(ref placeholder) => placeholder_expansions.push(
+                    Placeholder::expand(placeholder, position, language).unwrap_or_else(|| placeholder.to_string())
+                ),
+                WordNode::Word(_) => {}
+                _ => continue,
+            }
+
+            position += 1;
+        }
+
+        self.indexed_full_word = if placeholder_expansions.is_empty() {
+            None
+        } else {
+            Some(self.word_nodes.build_full_word().to_lowercase())
+        };
+        self.placeholder_expansions = placeholder_expansions;
+
+        self.indexed_romanized = transliterate::romanize(&self.indexed_word, language)
+            .map(|romanized| normalize::normalize_spelling(&romanized));
+        self.unbracketed_romanized = transliterate::romanize(&self.word_nodes.build_unbracketed_word(), language)
+            .map(|romanized| normalize::fold_diacritics(&romanized));
+        self.romanized = transliterate::romanize(&self.word_with_optional_parts(), language);
+
+        self.phonetic_algorithm = PhoneticAlgorithm::for_language(language);
+        self.indexed_phonetic = match self.indexed_romanized {
+            Some(ref indexed_romanized) => self.phonetic_algorithm.encode_words(indexed_romanized),
+            None => self.phonetic_algorithm.encode_words(&self.indexed_word),
+        };
+
+        if *language == Language::DE {
+            for tag in self.typed_genders() {
+                // Plural nouns always have the article `die`, e.g. `{m.pl}`
+                for article in tag.alternatives().iter().filter_map(|alternative| alternative.german_article()) {
+                    if !self.articles.contains(&article) {
+                        self.articles.push(article);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The German definite articles of the word, derived from its genders.
+    /// Empty, if the word is not German or has no gender.
+    ///
+    /// Syntax:
+    /// `Hund {m}` -> `["der"]`
+    /// `Angestellte {f} {m}` -> `["die", "der"]`
+    /// `Eltern {pl}` -> `["die"]`
+    ///
+    pub fn articles(&self) -> &[&'static str] {
+        &self.articles
+    }
+
+    /// The word with optional parts and its German definite articles, if it has any.
+    ///
+    /// Syntax:
+    /// `Wohnung {f}` -> `die Wohnung`
+    /// `Leute {pl}` -> `die Leute`
+    /// `Angestellte {f} {m}` -> `die/der Angestellte`
+    ///
+    pub fn with_articles(&self) -> Option<String> {
+        if self.articles.is_empty() {
+            None
+        } else {
+            Some(format!("{} {}", self.articles.join("/"), self.word_with_optional_parts()))
+        }
+    }
+
+    /// The word with optional parts in Latin script, if the word is written
+    /// in a language with a transliteration table (see `transliterate::romanize`).
+    ///
+    /// Syntax:
+    /// `(за) кошка` -> `(za) koshka`
+    ///
+    pub fn romanized(&self) -> Option<&str> {
+        self.romanized.as_deref()
+    }
+
+    /// The regional varieties of the word, e.g. `[British]` for `lorry [Br.]`.
+    /// Empty, if the word is not restricted to a region.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// The registers and style labels of the word, e.g. `[Colloquial, Pejorative]` for `Bulle {m} [ugs.] [pej.]`.
+    pub fn registers(&self) -> &[Register] {
+        &self.registers
+    }
+
+    pub(crate) fn has_gender(&self, gender: Gender) -> bool {
+        self.typed_genders().iter().any(|tag| tag.alternatives().iter().any(|alternative| alternative.has_gender(gender)))
+    }
+
+    pub(crate) fn has_any_gender(&self) -> bool {
+        self.typed_genders().iter().any(|tag| !tag.alternatives().is_empty())
+    }
+
+    /// Syntax:
+    /// `jdn. anrufen` -> `["jdn."]`
+    /// `to do sth.` -> `["sth."]`
+    /// `sich beeilen` -> `["sich"]`
+    ///
+    /// Indexing:
+    /// not for sorting and not a keyword (see `placeholder`)
+    ///
+    pub fn placeholders(&self) -> Vec<String> {
+        self.word_nodes.build_placeholders()
+    }
+
+    /// The displayed word with expanded placeholders.
+    ///
+    /// Syntax:
+    /// `jdm. (etw.) geben` -> `jemandem (etw.) geben`
+    /// `to give sb. sth.` -> `to give somebody something`
+    ///
+    pub fn with_expanded_placeholders(&self) -> String {
+        self.word_nodes.build_expanded_word(&self.placeholder_expansions)
     }
 
-    // TODO: make a searchable keyword
     /// Syntax:
     /// `<foo>`
     /// `<foo, bar>`
     ///
     /// Indexing:
-    /// not for sorting, but a keyword
+    /// not for sorting, but a keyword (see `QueryField::Acronyms`)
     ///
     pub fn acronyms(&self) -> Vec<String> {
         self.word_nodes.build_acronyms()
@@ -325,7 +1124,7 @@ impl DictWord {
     /// `[foo]`
     ///
     /// Indexing:
-    /// not for sorting and not a keyword
+    /// not for sorting and not a keyword, but searchable (see `QueryField::Comments`)
     ///
     pub fn comments(&self) -> Vec<String> {
         self.word_nodes.build_comments()
@@ -345,6 +1144,18 @@ impl DictWord {
         self.word_nodes.build_genders()
     }
 
+    /// The parsed gender tags, see `GenderTag`.
+    ///
+    /// Syntax:
+    /// `{m/f}` -> `[Known([m, f])]`
+    /// `{n} {sg}` -> `[Known([n]), Known([sg])]`
+    /// `{f.pl}` -> `[Known([f.pl])]`
+    /// `{ugs.}` -> `[Unknown("ugs.")]`
+    ///
+    pub fn typed_genders(&self) -> Vec<GenderTag> {
+        self.genders().iter().map(|tag| GenderTag::parse(tag)).collect()
+    }
+
     /// The word with optional parts
     ///
     /// Syntax:
@@ -365,10 +1176,19 @@ impl DictWord {
     pub fn plain_word(&self) -> String {
         self.word_nodes.build_plain_word()
     }
+
+    /// All variants of the word with and without each of its optional parts
+    ///
+    /// Syntax:
+    /// `(a) foo (b)` -> `foo`, `a foo`, `foo b`, `a foo b`
+    ///
+    pub fn expansions(&self) -> Vec<String> {
+        self.word_nodes.build_expansions()
+    }
 }
 
 /// Lists all available languages
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub enum Language {
     /// Albanian
     SQ,
@@ -574,7 +1394,7 @@ impl DictLanguagePair {
 
 /// Lists all available genders
 #[allow(missing_docs)]
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub enum Gender {
     Feminine,
     Masculine,
@@ -583,6 +1403,22 @@ pub enum Gender {
     Singular,
 }
 
+impl Gender {
+    /// The German definite article of nouns with this gender, e.g. `die` for `Feminine` and `Plural`.
+    ///
+    /// `Singular` has no article on its own.
+    pub fn german_article(self) -> Option<&'static str> {
+        use self::Gender::*;
+
+        match self {
+            Feminine | Plural => Some("die"),
+            Masculine => Some("der"),
+            Neuter => Some("das"),
+            Singular => None,
+        }
+    }
+}
+
 impl FromStr for Gender {
     type Err = DictError;
 
@@ -601,10 +1437,173 @@ impl FromStr for Gender {
     }
 }
 
+impl Display for Gender {
+    /// Formats the Gender as the abbreviation used by dict.cc, e.g. `f`
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::Gender::*;
+
+        f.write_str(match *self {
+            Feminine => "f",
+            Masculine => "m",
+            Neuter => "n",
+            Plural => "pl",
+            Singular => "sg",
+        })
+    }
+}
+
+/// Grammatical number of a noun, e.g. `pl` in `{f.pl}`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Number {
+    Singular,
+    Plural,
+}
+
+impl Display for Number {
+    /// Formats the Number as the abbreviation used by dict.cc, e.g. `pl`
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Number::Singular => f.write_str("sg"),
+            Number::Plural => f.write_str("pl"),
+        }
+    }
+}
+
+/// One alternative of a `GenderTag`, which combines a gender and a number,
+/// e.g. `f.pl` of `{f.pl}` or `m` of `{m/f}`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GenderAlternative {
+    /// The gender, i.e. `Feminine`, `Masculine` or `Neuter`, if the alternative has one.
+    pub gender: Option<Gender>,
+    /// The number, if the alternative has one.
+    pub number: Option<Number>,
+    /// The separator between gender and number as written in the tag, e.g. `.` of `f.pl`.
+    separator: String,
+}
+
+impl GenderAlternative {
+    /// Parses an alternative of a tag, e.g. `f.pl` or `n, sg`.
+    ///
+    /// Returns None, if the alternative is not made of at most one gender and at most one number.
+    fn parse(alternative: &str) -> Option<Self> {
+        let features: Vec<&str> = alternative.split(|c: char| c == '.' || c == ',' || c.is_whitespace())
+            .filter(|feature| !feature.is_empty())
+            .collect();
+
+        let separator = match features.as_slice() {
+            [first, second] => {
+                let first_end = alternative.find(first)? + first.len();
+                let second_start = first_end + alternative[first_end..].find(second)?;
+                alternative[first_end..second_start].to_string()
+            }
+            [_] => String::new(),
+            _ => return None,
+        };
+
+        let mut parsed = GenderAlternative {
+            gender: None,
+            number: None,
+            separator,
+        };
+
+        for feature in features {
+            let (gender, number) = match feature.to_lowercase().parse().ok()? {
+                Gender::Plural => (None, Some(Number::Plural)),
+                Gender::Singular => (None, Some(Number::Singular)),
+                gender => (Some(gender), None),
+            };
+
+            if (gender.is_some() && parsed.gender.is_some()) || (number.is_some() && parsed.number.is_some()) {
+                return None;
+            }
+
+            parsed.gender = parsed.gender.or(gender);
+            parsed.number = parsed.number.or(number);
+        }
+
+        Some(parsed)
+    }
+
+    /// Whether the alternative has the `gender`. The numbers `Plural` and `Singular` are compared to the number.
+    pub fn has_gender(&self, gender: Gender) -> bool {
+        match gender {
+            Gender::Plural => self.number == Some(Number::Plural),
+            Gender::Singular => self.number == Some(Number::Singular),
+            gender => self.gender == Some(gender),
+        }
+    }
+
+    /// The German definite article of nouns with this gender and number, e.g. `die` for `f` and for `m.pl`.
+    pub fn german_article(&self) -> Option<&'static str> {
+        match self.number {
+            Some(Number::Plural) => Gender::Plural.german_article(),
+            _ => self.gender.and_then(Gender::german_article),
+        }
+    }
+}
+
+impl Display for GenderAlternative {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (self.gender, self.number) {
+            (Some(gender), Some(number)) => write!(f, "{}{}{}", gender, self.separator, number),
+            (Some(gender), None) => write!(f, "{}", gender),
+            (None, Some(number)) => write!(f, "{}", number),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Structured gender and number information of a `{...}` tag of a `DictWord`.
+///
+/// The parsing is fault-tolerant: unknown tags are preserved instead of causing an error.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GenderTag {
+    /// A tag of alternatives separated by `/`, e.g. `{m/f}`,
+    /// each of which may combine a gender and a number, e.g. `{f.pl}` or `{n, sg}`.
+    Known(Vec<GenderAlternative>),
+    /// A tag, which is not made of genders and numbers, e.g. `{ugs.}`, without the braces.
+    Unknown(String),
+}
+
+impl GenderTag {
+    /// Parses the content of a `{...}` tag, e.g. `m/f`.
+    pub fn parse(tag: &str) -> Self {
+        let alternatives: Option<Vec<GenderAlternative>> = tag.split('/')
+            .map(GenderAlternative::parse)
+            .collect();
+
+        match alternatives {
+            Some(alternatives) => GenderTag::Known(alternatives),
+            None => GenderTag::Unknown(tag.to_string()),
+        }
+    }
+
+    /// The alternatives of the tag. Empty for an unknown tag.
+    pub fn alternatives(&self) -> &[GenderAlternative] {
+        match *self {
+            GenderTag::Known(ref alternatives) => alternatives,
+            GenderTag::Unknown(_) => &[],
+        }
+    }
+}
+
+impl Display for GenderTag {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            GenderTag::Known(ref alternatives) => {
+                let alternatives: Vec<String> = alternatives.iter().map(|alternative| alternative.to_string()).collect();
+
+                write!(f, "{{{}}}", alternatives.join("/"))
+            }
+            GenderTag::Unknown(ref tag) => write!(f, "{{{}}}", tag),
+        }
+    }
+}
 
 /// Lists all available `WordClasses`
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Eq, PartialEq, Debug, Ord, PartialOrd)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Ord, PartialOrd)]
 pub enum WordClass {
     Adjective,
     Adverb,
@@ -625,6 +1624,27 @@ impl WordClass {
     }
 }
 
+impl Display for WordClass {
+    /// Formats the WordClass as the abbreviation used by dict.cc, e.g. `past-p`
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::WordClass::*;
+
+        f.write_str(match *self {
+            Adjective => "adj",
+            Adverb => "adv",
+            Past => "past-p",
+            Verb => "verb",
+            PresentParticiple => "pres-p",
+            Preposition => "prep",
+            Conjunction => "conj",
+            Pronoun => "pron",
+            Prefix => "prefix",
+            Suffix => "suffix",
+            Noun => "noun",
+        })
+    }
+}
+
 impl FromStr for WordClass {
     type Err = DictError;
 
@@ -648,3 +1668,171 @@ impl FromStr for WordClass {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn left_words<E: Borrow<DictEntry>>(entries: &[E]) -> Vec<String> {
+        entries.iter().map(|entry| entry.borrow().left_word.to_string()).collect()
+    }
+
+    #[test]
+    fn test_execute_paging() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        let ranked = left_words(dict.query("foo").execute().unwrap().sorted_by_relevance().entries());
+        assert_eq!(6, ranked.len());
+
+        // The page is taken from the ranked entries, not from the database order
+        let mut query = dict.query("foo");
+        query.limit(2);
+        assert_eq!(ranked[..2].to_vec(), left_words(query.execute().unwrap().entries()));
+
+        query.offset(2);
+        assert_eq!(ranked[2..4].to_vec(), left_words(query.execute().unwrap().entries()));
+
+        query.offset(5).limit(10);
+        assert_eq!(ranked[5..].to_vec(), left_words(query.execute().unwrap().entries()));
+
+        query.offset(100);
+        assert!(query.execute().unwrap().entries().is_empty());
+
+        query.offset(0).limit(0);
+        assert!(query.execute().unwrap().entries().is_empty());
+    }
+
+    #[test]
+    fn test_to_owned_result() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+
+        let borrowed = dict.query("foo").execute().unwrap();
+        let owned = borrowed.to_owned_result();
+
+        assert_eq!(borrowed.entries().len(), owned.entries().len());
+        assert!(borrowed.entries().iter().zip(owned.entries()).all(|(&borrowed, owned)| borrowed == owned));
+        assert_eq!(borrowed.relevances(), owned.relevances());
+        assert_eq!(borrowed.matches(), owned.matches());
+
+        assert_eq!(owned, owned.to_owned_result());
+    }
+
+    #[test]
+    fn test_query_fields() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        let query_field = |term: &str, query_field: &str| {
+            let mut query = dict.query(term);
+            query.set_field(query_field.parse().unwrap());
+            left_words(query.execute().unwrap().entries())
+        };
+
+        let acronym_entries = vec!["(optionales) Wort & {f} [Kommentar] <Akronym, anderes Akronym>", "(optionales) Wort {f} [Kommentar] <Akronym, anderes Akronym>"];
+
+        // Acronyms are keywords, so they must match completely
+        assert_eq!(acronym_entries, query_field("akronym", "acronym"));
+        assert_eq!(acronym_entries, query_field("anderes akronym", "acronym"));
+        assert!(query_field("anderes", "acronym").is_empty());
+        assert!(query_field("akronym", "word").is_empty());
+
+        assert_eq!(acronym_entries, query_field("kommentar", "comment"));
+        assert_eq!(acronym_entries, query_field("comment", "comment"));
+        assert!(query_field("kommentar", "word").is_empty());
+
+        assert_eq!(acronym_entries, query_field("akronym", "all"));
+        assert_eq!(acronym_entries, query_field("kommentar", "all"));
+        assert_eq!(vec!["Verb", "foo Verb"], query_field("verb", "all"));
+
+        assert!("foo".parse::<QueryField>().is_err());
+    }
+
+    #[test]
+    fn test_iter_paging() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        let all = left_words(dict.query("foo").execute().unwrap().entries());
+        let iterated = |query: &DictQuery| left_words(&query.iter().unwrap().collect::<Vec<_>>());
+
+        let mut query = dict.query("foo");
+        assert_eq!(all, iterated(&query));
+
+        // The iterator pages in the order of the database file
+        query.offset(1).limit(2);
+        assert_eq!(all[1..3].to_vec(), iterated(&query));
+
+        query.offset(100);
+        assert!(iterated(&query).is_empty());
+
+        query.offset(0).limit(0);
+        assert!(iterated(&query).is_empty());
+
+        // The iterator is lazy and can be stopped early
+        assert_eq!(all[..1].to_vec(), left_words(&dict.query("foo").iter().unwrap().take(1).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let dict = Dict::create("tests/database/test_database_case.txt").unwrap();
+        let case_sensitive = |term: &str, case_sensitive: bool| {
+            let mut query = dict.query(term);
+            query.case_sensitive(case_sensitive);
+            left_words(query.execute().unwrap().entries())
+        };
+
+        assert_eq!(vec!["Essen {n}", "essen"], case_sensitive("essen", false));
+        assert_eq!(vec!["Essen {n}", "essen"], case_sensitive("Essen", false));
+
+        assert_eq!(vec!["essen"], case_sensitive("essen", true));
+        assert_eq!(vec!["Essen {n}"], case_sensitive("Essen", true));
+        assert!(case_sensitive("ESSEN", true).is_empty());
+    }
+
+    #[test]
+    fn test_too_many_columns() {
+        let dict = Dict::create("tests/database/test_database_columns.txt").unwrap();
+
+        // The record with a stray fifth column is dropped, the other records are loaded
+        assert_eq!(vec!["Haus {n}"], left_words(&dict.entries));
+        assert_eq!(1, dict.load_stats.too_many_columns);
+        assert_eq!(0, dict.load_stats.incomplete_records);
+    }
+
+    #[test]
+    fn test_gender_tag_parse() {
+        use self::Gender::*;
+
+        let alternative = |gender: Option<Gender>, number: Option<Number>, separator: &str| GenderAlternative {
+            gender,
+            number,
+            separator: separator.to_string(),
+        };
+
+        let data = vec![
+            ("f", GenderTag::Known(vec![alternative(Some(Feminine), None, "")])),
+            ("m/f", GenderTag::Known(vec![alternative(Some(Masculine), None, ""), alternative(Some(Feminine), None, "")])),
+            ("f.pl", GenderTag::Known(vec![alternative(Some(Feminine), Some(Number::Plural), ".")])),
+            ("n, sg", GenderTag::Known(vec![alternative(Some(Neuter), Some(Number::Singular), ", ")])),
+            ("PL.", GenderTag::Known(vec![alternative(None, Some(Number::Plural), "")])),
+            ("ugs.", GenderTag::Unknown("ugs.".to_string())),
+            ("m/ugs.", GenderTag::Unknown("m/ugs.".to_string())),
+            ("m.f", GenderTag::Unknown("m.f".to_string())),
+            ("m/", GenderTag::Unknown("m/".to_string())),
+            ("", GenderTag::Unknown("".to_string())),
+        ];
+
+        for (tag, expected) in data {
+            assert_eq!(expected, GenderTag::parse(tag), "tag: {:?}", tag);
+        }
+    }
+
+    #[test]
+    fn test_gender_tag_round_trip() {
+        for &tag in &["f", "pl", "f.pl", "m/f", "m/n", "n, sg", "m/f.pl", "ugs."] {
+            assert_eq!(format!("{{{}}}", tag), GenderTag::parse(tag).to_string(), "tag: {:?}", tag);
+        }
+
+        let alternatives = GenderTag::parse("m/f.pl");
+        let alternatives = alternatives.alternatives();
+        assert!(alternatives[0].has_gender(Gender::Masculine) && !alternatives[0].has_gender(Gender::Plural));
+        assert!(alternatives[1].has_gender(Gender::Feminine) && alternatives[1].has_gender(Gender::Plural));
+        assert_eq!(Some("der"), alternatives[0].german_article());
+        assert_eq!(Some("die"), GenderTag::parse("m.pl").alternatives()[0].german_article());
+    }
+}
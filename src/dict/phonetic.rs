@@ -0,0 +1,741 @@
+//! Phonetic codes of words, used by `QueryType::Phonetic`.
+//!
+//! Words, which sound similar, get the same code:
+//!
+//! - German: [Kölner Phonetik](https://de.wikipedia.org/wiki/K%C3%B6lner_Phonetik), `Philosophie` -> `3583`
+//! - English: [Double Metaphone](https://en.wikipedia.org/wiki/Metaphone#Double_Metaphone), `night` -> `NT`
+//! - Other languages: a simple language independent code, which merges similar consonants and drops vowels
+//!
+//! Every token (space or hyphen separated word) is encoded separately.
+
+use dict::Language;
+use dict::normalize::normalize_spelling;
+use std::ops::Range;
+
+/// The phonetic algorithm used for the words of a language.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub(crate) enum PhoneticAlgorithm {
+    Cologne,
+    DoubleMetaphone,
+    Simple,
+}
+
+impl PhoneticAlgorithm {
+    pub(crate) fn for_language(language: &Language) -> Self {
+        match *language {
+            Language::DE => PhoneticAlgorithm::Cologne,
+            Language::EN => PhoneticAlgorithm::DoubleMetaphone,
+            _ => PhoneticAlgorithm::Simple,
+        }
+    }
+
+    /// The alternative codes of a single token.
+    pub(crate) fn encode(self, token: &str) -> Vec<String> {
+        match self {
+            PhoneticAlgorithm::Cologne => vec![cologne(token)],
+            PhoneticAlgorithm::DoubleMetaphone => {
+                let (primary, alternate) = double_metaphone(token);
+
+                if primary == alternate {
+                    vec![primary]
+                } else {
+                    vec![primary, alternate]
+                }
+            }
+            PhoneticAlgorithm::Simple => vec![simple(token)],
+        }
+    }
+
+    /// The codes of every token of `text` (see `tokenize`).
+    pub(crate) fn encode_words(self, text: &str) -> Vec<Vec<String>> {
+        tokenize(text).into_iter()
+            .map(|token| self.encode(&text[token]))
+            .collect()
+    }
+}
+
+/// The byte ranges of the tokens of `text`, which contain at least one letter.
+pub(crate) fn tokenize(text: &str) -> Vec<Range<usize>> {
+    let mut tokens = vec![];
+    let mut start = 0;
+
+    for (i, c) in text.char_indices().chain(Some((text.len(), ' '))) {
+        if c.is_whitespace() || c == '-' {
+            if text[start..i].chars().any(char::is_alphabetic) {
+                tokens.push(start..i);
+            }
+
+            start = i + c.len_utf8();
+        }
+    }
+
+    tokens
+}
+
+/// The token indices, at which the encoded query occurs in the encoded word.
+///
+/// Two tokens match, if they share one of their alternative codes.
+pub(crate) fn find_matches(query_codes: &[Vec<String>], word_codes: &[Vec<String>]) -> Vec<usize> {
+    if query_codes.is_empty() || query_codes.len() > word_codes.len() {
+        return vec![];
+    }
+
+    (0..word_codes.len() - query_codes.len() + 1)
+        .filter(|&start| query_codes.iter().zip(&word_codes[start..]).all(|(query_token, word_token)| {
+            query_token.iter().any(|code| word_token.contains(code))
+        }))
+        .collect()
+}
+
+/// The lowercase letters of `token` without diacritics.
+fn letters(token: &str) -> Vec<char> {
+    normalize_spelling(token).chars()
+        .filter(|c| c.is_ascii_lowercase())
+        .collect()
+}
+
+/// Kölner Phonetik
+fn cologne(token: &str) -> String {
+    let chars = letters(token);
+    let mut digits = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = if i > 0 { chars[i - 1] } else { '\0' };
+        let next = chars.get(i + 1).cloned().unwrap_or('\0');
+
+        let digit = match c {
+            'a' | 'e' | 'i' | 'j' | 'o' | 'u' | 'y' => "0",
+            'b' => "1",
+            'p' => if next == 'h' { "3" } else { "1" },
+            'd' | 't' => if "csz".contains(next) { "8" } else { "2" },
+            'f' | 'v' | 'w' => "3",
+            'g' | 'k' | 'q' => "4",
+            'c' => if i == 0 {
+                if "ahkloqrux".contains(next) { "4" } else { "8" }
+            } else if prev == 's' || prev == 'z' {
+                "8"
+            } else if "ahkoqux".contains(next) {
+                "4"
+            } else {
+                "8"
+            },
+            'x' => if prev == 'c' || prev == 'k' || prev == 'q' { "8" } else { "48" },
+            'l' => "5",
+            'm' | 'n' => "6",
+            'r' => "7",
+            's' | 'z' => "8",
+            // `h`
+            _ => "",
+        };
+
+        digits.push_str(digit);
+    }
+
+    let mut code = String::new();
+    let mut last = None;
+
+    for (i, digit) in digits.chars().enumerate() {
+        // Collapse repeated digits and remove vowels, except at the beginning
+        if last != Some(digit) && (digit != '0' || i == 0) {
+            code.push(digit);
+        }
+
+        last = Some(digit);
+    }
+
+    code
+}
+
+/// Language independent code: similar consonants are merged, vowels are dropped except at the beginning.
+fn simple(token: &str) -> String {
+    let chars = letters(token);
+    let mut code = String::new();
+    let mut last = None;
+
+    for (i, &c) in chars.iter().enumerate() {
+        let next = chars.get(i + 1).cloned();
+
+        let class = match c {
+            'p' if next == Some('h') => 'f',
+            'h' if i > 0 => continue,
+            'b' | 'p' => 'p',
+            'c' | 'g' | 'k' | 'q' => 'k',
+            'd' | 't' => 't',
+            'f' | 'v' | 'w' => 'f',
+            's' | 'x' | 'z' => 's',
+            'a' | 'e' | 'i' | 'o' | 'u' | 'y' => 'a',
+            c => c,
+        };
+
+        if last != Some(class) && (class != 'a' || i == 0) {
+            code.push(class);
+        }
+
+        last = Some(class);
+    }
+
+    code
+}
+
+/// The maximal length of a Double Metaphone code.
+const DOUBLE_METAPHONE_LENGTH: usize = 4;
+
+/// Double Metaphone, returns the primary and the alternate code.
+///
+/// Port of the original algorithm by Lawrence Philips.
+fn double_metaphone(token: &str) -> (String, String) {
+    let mut encoder = DoubleMetaphone {
+        chars: letters(token).into_iter().map(|c| c.to_ascii_uppercase()).collect(),
+        primary: String::new(),
+        alternate: String::new(),
+    };
+
+    encoder.encode();
+
+    let DoubleMetaphone { mut primary, mut alternate, .. } = encoder;
+    primary.truncate(DOUBLE_METAPHONE_LENGTH);
+    alternate.truncate(DOUBLE_METAPHONE_LENGTH);
+
+    (primary, alternate)
+}
+
+struct DoubleMetaphone {
+    chars: Vec<char>,
+    primary: String,
+    alternate: String,
+}
+
+impl DoubleMetaphone {
+    fn len(&self) -> isize {
+        self.chars.len() as isize
+    }
+
+    fn char_at(&self, index: isize) -> char {
+        if index >= 0 && index < self.len() {
+            self.chars[index as usize]
+        } else {
+            '\0'
+        }
+    }
+
+    fn is_vowel_at(&self, index: isize) -> bool {
+        "AEIOUY".contains(self.char_at(index))
+    }
+
+    /// Whether the substring at `start` with `length` characters is one of `options`.
+    fn contains(&self, start: isize, length: usize, options: &[&str]) -> bool {
+        if start < 0 || start as usize + length > self.chars.len() {
+            return false;
+        }
+
+        let substring: String = self.chars[start as usize..start as usize + length].iter().collect();
+        options.contains(&substring.as_str())
+    }
+
+    fn is_slavo_germanic(&self) -> bool {
+        let word: String = self.chars.iter().collect();
+        word.contains('W') || word.contains('K') || word.contains("CZ") || word.contains("WITZ")
+    }
+
+    fn add(&mut self, code: &str) {
+        self.primary.push_str(code);
+        self.alternate.push_str(code);
+    }
+
+    fn add_both(&mut self, primary: &str, alternate: &str) {
+        self.primary.push_str(primary);
+        self.alternate.push_str(alternate);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= DOUBLE_METAPHONE_LENGTH && self.alternate.len() >= DOUBLE_METAPHONE_LENGTH
+    }
+
+    fn encode(&mut self) {
+        let slavo_germanic = self.is_slavo_germanic();
+
+        let mut index = if self.contains(0, 2, &["GN", "KN", "PN", "WR", "PS"]) { 1 } else { 0 };
+
+        while !self.is_complete() && index < self.len() {
+            index = match self.char_at(index) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if index == 0 {
+                        self.add("A");
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.add("P");
+                    if self.char_at(index + 1) == 'B' { index + 2 } else { index + 1 }
+                }
+                'C' => self.handle_c(index),
+                'D' => self.handle_d(index),
+                'F' => {
+                    self.add("F");
+                    if self.char_at(index + 1) == 'F' { index + 2 } else { index + 1 }
+                }
+                'G' => self.handle_g(index, slavo_germanic),
+                'H' => self.handle_h(index),
+                'J' => self.handle_j(index, slavo_germanic),
+                'K' => {
+                    self.add("K");
+                    if self.char_at(index + 1) == 'K' { index + 2 } else { index + 1 }
+                }
+                'L' => self.handle_l(index),
+                'M' => {
+                    self.add("M");
+                    if self.condition_m0(index) { index + 2 } else { index + 1 }
+                }
+                'N' => {
+                    self.add("N");
+                    if self.char_at(index + 1) == 'N' { index + 2 } else { index + 1 }
+                }
+                'P' => self.handle_p(index),
+                'Q' => {
+                    self.add("K");
+                    if self.char_at(index + 1) == 'Q' { index + 2 } else { index + 1 }
+                }
+                'R' => self.handle_r(index, slavo_germanic),
+                'S' => self.handle_s(index, slavo_germanic),
+                'T' => self.handle_t(index),
+                'V' => {
+                    self.add("F");
+                    if self.char_at(index + 1) == 'V' { index + 2 } else { index + 1 }
+                }
+                'W' => self.handle_w(index),
+                'X' => self.handle_x(index),
+                'Z' => self.handle_z(index, slavo_germanic),
+                _ => index + 1,
+            };
+        }
+    }
+
+    fn handle_c(&mut self, index: isize) -> isize {
+        if self.condition_c0(index) {
+            self.add("K");
+            index + 2
+        } else if index == 0 && self.contains(index, 6, &["CAESAR"]) {
+            self.add("S");
+            index + 2
+        } else if self.contains(index, 2, &["CH"]) {
+            self.handle_ch(index)
+        } else if self.contains(index, 2, &["CZ"]) && !self.contains(index - 2, 4, &["WICZ"]) {
+            self.add_both("S", "X");
+            index + 2
+        } else if self.contains(index + 1, 3, &["CIA"]) {
+            self.add("X");
+            index + 3
+        } else if self.contains(index, 2, &["CC"]) && !(index == 1 && self.char_at(0) == 'M') {
+            self.handle_cc(index)
+        } else if self.contains(index, 2, &["CK", "CG", "CQ"]) {
+            self.add("K");
+            index + 2
+        } else if self.contains(index, 2, &["CI", "CE", "CY"]) {
+            if self.contains(index, 3, &["CIO", "CIE", "CIA"]) {
+                self.add_both("S", "X");
+            } else {
+                self.add("S");
+            }
+            index + 2
+        } else {
+            self.add("K");
+
+            if self.contains(index + 1, 1, &["C", "K", "Q"]) && !self.contains(index + 1, 2, &["CE", "CI"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn condition_c0(&self, index: isize) -> bool {
+        if self.contains(index, 4, &["CHIA"]) {
+            true
+        } else if index <= 1 || self.is_vowel_at(index - 2) || !self.contains(index - 1, 3, &["ACH"]) {
+            false
+        } else {
+            let c = self.char_at(index + 2);
+            (c != 'I' && c != 'E') || self.contains(index - 2, 6, &["BACHER", "MACHER"])
+        }
+    }
+
+    fn handle_cc(&mut self, index: isize) -> isize {
+        if self.contains(index + 2, 1, &["I", "E", "H"]) && !self.contains(index + 2, 2, &["HU"]) {
+            if (index == 1 && self.char_at(index - 1) == 'A') || self.contains(index - 1, 5, &["UCCEE", "UCCES"]) {
+                self.add("KS");
+            } else {
+                self.add("X");
+            }
+            index + 3
+        } else {
+            self.add("K");
+            index + 2
+        }
+    }
+
+    fn handle_ch(&mut self, index: isize) -> isize {
+        if index > 0 && self.contains(index, 4, &["CHAE"]) {
+            self.add_both("K", "X");
+        } else if self.condition_ch0(index) || self.condition_ch1(index) {
+            self.add("K");
+        } else if index > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.add("K");
+            } else {
+                self.add_both("X", "K");
+            }
+        } else {
+            self.add("X");
+        }
+
+        index + 2
+    }
+
+    fn condition_ch0(&self, index: isize) -> bool {
+        index == 0
+            && (self.contains(index + 1, 5, &["HARAC", "HARIS"]) || self.contains(index + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn condition_ch1(&self, index: isize) -> bool {
+        self.contains(0, 3, &["SCH"])
+            || self.contains(index - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(index + 2, 1, &["T", "S"])
+            || ((self.contains(index - 1, 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.contains(index + 2, 1, &["L", "R", "N", "M", "B", "H", "F", "V", "W"]) || index + 1 == self.len() - 1))
+    }
+
+    fn handle_d(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["DG"]) {
+            if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+                self.add("J");
+                index + 3
+            } else {
+                self.add("TK");
+                index + 2
+            }
+        } else if self.contains(index, 2, &["DT", "DD"]) {
+            self.add("T");
+            index + 2
+        } else {
+            self.add("T");
+            index + 1
+        }
+    }
+
+    fn handle_g(&mut self, index: isize, slavo_germanic: bool) -> isize {
+        if self.char_at(index + 1) == 'H' {
+            self.handle_gh(index)
+        } else if self.char_at(index + 1) == 'N' {
+            if index == 1 && self.is_vowel_at(0) && !slavo_germanic {
+                self.add_both("KN", "N");
+            } else if !self.contains(index + 2, 2, &["EY"]) && self.char_at(index + 1) != 'Y' && !slavo_germanic {
+                self.add_both("N", "KN");
+            } else {
+                self.add("KN");
+            }
+            index + 2
+        } else if self.contains(index + 1, 2, &["LI"]) && !slavo_germanic {
+            self.add_both("KL", "L");
+            index + 2
+        } else if (index == 0
+            && (self.char_at(index + 1) == 'Y'
+                || self.contains(index + 1, 2, &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"])))
+            || ((self.contains(index + 1, 2, &["ER"]) || self.char_at(index + 1) == 'Y')
+                && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+                && !self.contains(index - 1, 1, &["E", "I"])
+                && !self.contains(index - 1, 3, &["RGY", "OGY"])) {
+            self.add_both("K", "J");
+            index + 2
+        } else if self.contains(index + 1, 1, &["E", "I", "Y"]) || self.contains(index - 1, 4, &["AGGI", "OGGI"]) {
+            if self.contains(0, 4, &["VAN ", "VON "]) || self.contains(0, 3, &["SCH"]) || self.contains(index + 1, 2, &["ET"]) {
+                self.add("K");
+            } else if self.contains(index + 1, 3, &["IER"]) {
+                self.add("J");
+            } else {
+                self.add_both("J", "K");
+            }
+            index + 2
+        } else if self.char_at(index + 1) == 'G' {
+            self.add("K");
+            index + 2
+        } else {
+            self.add("K");
+            index + 1
+        }
+    }
+
+    fn handle_gh(&mut self, index: isize) -> isize {
+        if index > 0 && !self.is_vowel_at(index - 1) {
+            self.add("K");
+        } else if index == 0 {
+            if self.char_at(index + 2) == 'I' {
+                self.add("J");
+            } else {
+                self.add("K");
+            }
+        } else if (index > 1 && self.contains(index - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.contains(index - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.contains(index - 4, 1, &["B", "H"])) {
+            // e.g. "hugh", "bough", "broughton": silent
+        } else if index > 2 && self.char_at(index - 1) == 'U' && self.contains(index - 3, 1, &["C", "G", "L", "R", "T"]) {
+            // e.g. "laugh", "cough", "tough"
+            self.add("F");
+        } else if self.char_at(index - 1) != 'I' {
+            self.add("K");
+        }
+
+        index + 2
+    }
+
+    fn handle_h(&mut self, index: isize) -> isize {
+        // Only keep `h` at the beginning or between vowels
+        if (index == 0 || self.is_vowel_at(index - 1)) && self.is_vowel_at(index + 1) {
+            self.add("H");
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_j(&mut self, index: isize, slavo_germanic: bool) -> isize {
+        if self.contains(index, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            if (index == 0 && self.char_at(index + 4) == ' ') || self.len() == 4 || self.contains(0, 4, &["SAN "]) {
+                self.add("H");
+            } else {
+                self.add_both("J", "H");
+            }
+            return index + 1;
+        }
+
+        if index == 0 {
+            self.add_both("J", "A");
+        } else if self.is_vowel_at(index - 1) && !slavo_germanic && (self.char_at(index + 1) == 'A' || self.char_at(index + 1) == 'O') {
+            self.add_both("J", "H");
+        } else if index == self.len() - 1 {
+            self.add_both("J", "");
+        } else if !self.contains(index + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"]) && !self.contains(index - 1, 1, &["S", "K", "L"]) {
+            self.add("J");
+        }
+
+        if self.char_at(index + 1) == 'J' { index + 2 } else { index + 1 }
+    }
+
+    fn handle_l(&mut self, index: isize) -> isize {
+        if self.char_at(index + 1) == 'L' {
+            if self.condition_l0(index) {
+                self.add_both("L", "");
+            } else {
+                self.add("L");
+            }
+            index + 2
+        } else {
+            self.add("L");
+            index + 1
+        }
+    }
+
+    fn condition_l0(&self, index: isize) -> bool {
+        let len = self.len();
+
+        (index == len - 3 && self.contains(index - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.contains(len - 2, 2, &["AS", "OS"]) || self.contains(len - 1, 1, &["A", "O"]))
+                && self.contains(index - 1, 4, &["ALLE"]))
+    }
+
+    fn condition_m0(&self, index: isize) -> bool {
+        self.char_at(index + 1) == 'M'
+            || (self.contains(index - 1, 3, &["UMB"]) && (index + 1 == self.len() - 1 || self.contains(index + 2, 2, &["ER"])))
+    }
+
+    fn handle_p(&mut self, index: isize) -> isize {
+        if self.char_at(index + 1) == 'H' {
+            self.add("F");
+            index + 2
+        } else {
+            self.add("P");
+            if self.contains(index + 1, 1, &["P", "B"]) { index + 2 } else { index + 1 }
+        }
+    }
+
+    fn handle_r(&mut self, index: isize, slavo_germanic: bool) -> isize {
+        // French, e.g. "rogier"
+        if index == self.len() - 1 && !slavo_germanic
+            && self.contains(index - 2, 2, &["IE"]) && !self.contains(index - 4, 2, &["ME", "MA"]) {
+            self.add_both("", "R");
+        } else {
+            self.add("R");
+        }
+
+        if self.char_at(index + 1) == 'R' { index + 2 } else { index + 1 }
+    }
+
+    fn handle_s(&mut self, index: isize, slavo_germanic: bool) -> isize {
+        if self.contains(index - 1, 3, &["ISL", "YSL"]) {
+            // e.g. "island", "isle": silent
+            index + 1
+        } else if index == 0 && self.contains(index, 5, &["SUGAR"]) {
+            self.add_both("X", "S");
+            index + 1
+        } else if self.contains(index, 2, &["SH"]) {
+            if self.contains(index + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add("S");
+            } else {
+                self.add("X");
+            }
+            index + 2
+        } else if self.contains(index, 3, &["SIO", "SIA"]) || self.contains(index, 4, &["SIAN"]) {
+            if slavo_germanic {
+                self.add("S");
+            } else {
+                self.add_both("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.contains(index + 1, 1, &["M", "N", "L", "W"])) || self.contains(index + 1, 1, &["Z"]) {
+            self.add_both("S", "X");
+            if self.contains(index + 1, 1, &["Z"]) { index + 2 } else { index + 1 }
+        } else if self.contains(index, 2, &["SC"]) {
+            self.handle_sc(index)
+        } else {
+            // French, e.g. "resnais", "artois"
+            if index == self.len() - 1 && self.contains(index - 2, 2, &["AI", "OI"]) {
+                self.add_both("", "S");
+            } else {
+                self.add("S");
+            }
+            if self.contains(index + 1, 1, &["S", "Z"]) { index + 2 } else { index + 1 }
+        }
+    }
+
+    fn handle_sc(&mut self, index: isize) -> isize {
+        if self.char_at(index + 2) == 'H' {
+            if self.contains(index + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                if self.contains(index + 3, 2, &["ER", "EN"]) {
+                    self.add_both("X", "SK");
+                } else {
+                    self.add("SK");
+                }
+            } else if index == 0 && !self.is_vowel_at(3) && self.char_at(3) != 'W' {
+                self.add_both("X", "S");
+            } else {
+                self.add("X");
+            }
+        } else if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+            self.add("S");
+        } else {
+            self.add("SK");
+        }
+
+        index + 3
+    }
+
+    fn handle_t(&mut self, index: isize) -> isize {
+        if self.contains(index, 4, &["TION"]) || self.contains(index, 3, &["TIA", "TCH"]) {
+            self.add("X");
+            index + 3
+        } else if self.contains(index, 2, &["TH"]) || self.contains(index, 3, &["TTH"]) {
+            if self.contains(index + 2, 2, &["OM", "AM"]) || self.contains(0, 4, &["VAN ", "VON "]) || self.contains(0, 3, &["SCH"]) {
+                self.add("T");
+            } else {
+                self.add_both("0", "T");
+            }
+            index + 2
+        } else {
+            self.add("T");
+            if self.contains(index + 1, 1, &["T", "D"]) { index + 2 } else { index + 1 }
+        }
+    }
+
+    fn handle_w(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["WR"]) {
+            self.add("R");
+            return index + 2;
+        }
+
+        if index == 0 && (self.is_vowel_at(index + 1) || self.contains(index, 2, &["WH"])) {
+            if self.is_vowel_at(index + 1) {
+                self.add_both("A", "F");
+            } else {
+                self.add("A");
+            }
+            index + 1
+        } else if (index == self.len() - 1 && self.is_vowel_at(index - 1))
+            || self.contains(index - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"]) {
+            self.add_both("", "F");
+            index + 1
+        } else if self.contains(index, 4, &["WICZ", "WITZ"]) {
+            self.add_both("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_x(&mut self, index: isize) -> isize {
+        if index == 0 {
+            self.add("S");
+            return index + 1;
+        }
+
+        // French, e.g. "breaux"
+        let is_silent = index == self.len() - 1
+            && (self.contains(index - 3, 3, &["IAU", "EAU"]) || self.contains(index - 2, 2, &["AU", "OU"]));
+
+        if !is_silent {
+            self.add("KS");
+        }
+
+        if self.contains(index + 1, 1, &["C", "X"]) { index + 2 } else { index + 1 }
+    }
+
+    fn handle_z(&mut self, index: isize, slavo_germanic: bool) -> isize {
+        if self.char_at(index + 1) == 'H' {
+            self.add("J");
+            return index + 2;
+        }
+
+        if self.contains(index + 1, 2, &["ZO", "ZI", "ZA"]) || (slavo_germanic && index > 0 && self.char_at(index - 1) != 'T') {
+            self.add_both("S", "TS");
+        } else {
+            self.add("S");
+        }
+
+        if self.char_at(index + 1) == 'Z' { index + 2 } else { index + 1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cologne() {
+        assert_eq!("3583", cologne("Philosophie"));
+        assert_eq!("3583", cologne("filosofie"));
+        assert_eq!("65752682", cologne("Müller-Lüdenscheidt"));
+        assert_eq!("4837", cologne("Xaver"));
+        assert_eq!(cologne("Meier"), cologne("Mayr"));
+    }
+
+    #[test]
+    fn test_double_metaphone() {
+        assert_eq!(("NT".to_string(), "NT".to_string()), double_metaphone("night"));
+        assert_eq!(("NT".to_string(), "NT".to_string()), double_metaphone("nite"));
+        assert_eq!(("FLSF".to_string(), "FLSF".to_string()), double_metaphone("philosophy"));
+        assert_eq!(("SM0".to_string(), "XMT".to_string()), double_metaphone("Smith"));
+        assert_eq!(("KF".to_string(), "KF".to_string()), double_metaphone("cough"));
+        assert_eq!(("AKSS".to_string(), "AKSS".to_string()), double_metaphone("exist"));
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let algorithm = PhoneticAlgorithm::DoubleMetaphone;
+        let word = algorithm.encode_words("good night & good luck");
+
+        assert_eq!(vec![1], find_matches(&algorithm.encode_words("nite"), &word));
+        assert_eq!(vec![0, 2], find_matches(&algorithm.encode_words("gud"), &word));
+        assert_eq!(vec![0], find_matches(&algorithm.encode_words("gud nite"), &word));
+        assert!(find_matches(&algorithm.encode_words("day"), &word).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+//! Placeholders of verb phrases, e.g. `jdn.` in `jdn. anrufen` or `sth.` in `to do sth.`,
+//! and reflexive pronouns, e.g. `sich` in `sich beeilen`.
+//!
+//! The placeholders are marked as `WordNode::Placeholder` after parsing, depending on the language of the word.
+//! They are not indexed and not counted as words, so that `anrufen` matches `jdn. anrufen` exactly.
+//! The full form with the placeholders is matched as well, e.g. `jdn. anrufen` or `sb.`.
+
+use super::*;
+
+/// Grammatical case of the object represented by a placeholder.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Case {
+    Nominative,
+    Accusative,
+    Dative,
+    Genitive,
+}
+
+/// A placeholder of the dict.cc syntax.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct Placeholder {
+    /// The abbreviation as written in the word, e.g. `jdm.`
+    pub abbreviation: &'static str,
+    /// The expanded placeholder, e.g. `jemandem`
+    pub expansion: &'static str,
+    /// The case of the object, if the placeholder determines it, e.g. `Dative` for `jdm.`
+    pub case: Option<Case>,
+    /// Whether the placeholder is a reflexive pronoun, e.g. `sich`
+    pub reflexive: bool,
+}
+
+const GERMAN: &[Placeholder] = &[
+    Placeholder { abbreviation: "jd.", expansion: "jemand", case: Some(Case::Nominative), reflexive: false },
+    Placeholder { abbreviation: "jdn.", expansion: "jemanden", case: Some(Case::Accusative), reflexive: false },
+    Placeholder { abbreviation: "jdm.", expansion: "jemandem", case: Some(Case::Dative), reflexive: false },
+    Placeholder { abbreviation: "jds.", expansion: "jemandes", case: Some(Case::Genitive), reflexive: false },
+    Placeholder { abbreviation: "etw.", expansion: "etwas", case: None, reflexive: false },
+    Placeholder { abbreviation: "sich", expansion: "sich", case: None, reflexive: true },
+];
+
+const ENGLISH: &[Placeholder] = &[
+    Placeholder { abbreviation: "sb.", expansion: "somebody", case: None, reflexive: false },
+    Placeholder { abbreviation: "sb.'s", expansion: "somebody's", case: Some(Case::Genitive), reflexive: false },
+    Placeholder { abbreviation: "sth.", expansion: "something", case: None, reflexive: false },
+    Placeholder { abbreviation: "oneself", expansion: "oneself", case: None, reflexive: true },
+];
+
+impl Placeholder {
+    /// Returns the placeholders of a word written in `language`, e.g. `[jdn., etw.]` for `jdn./etw.`
+    ///
+    /// `position` is the position of the word within the `DictWord`.
+    /// The German reflexive pronoun is only a placeholder in front of the verb,
+    /// e.g. in `sich beeilen`, but not in `an sich`.
+    ///
+    /// Returns None, if the word is not a placeholder.
+    pub fn parse_word(word: &str, position: usize, language: &Language) -> Option<Vec<Placeholder>> {
+        let table = match *language {
+            Language::DE => GERMAN,
+            Language::EN => ENGLISH,
+            _ => return None,
+        };
+
+        word.split('/')
+            .map(|part| table.iter().find(|placeholder| placeholder.abbreviation == part).cloned())
+            .map(|placeholder| match placeholder {
+                Some(placeholder) if placeholder.reflexive && *language == Language::DE && position > 0 => None,
+                placeholder => placeholder,
+            })
+            .collect()
+    }
+
+    /// Expands the placeholders of a word, e.g. `jdm.` -> `jemandem` or `jdn./etw.` -> `jemanden/etwas`.
+    ///
+    /// Returns None, if the word is not a placeholder.
+    pub fn expand(word: &str, position: usize, language: &Language) -> Option<String> {
+        Placeholder::parse_word(word, position, language).map(|placeholders| {
+            placeholders.iter()
+                .map(|placeholder| placeholder.expansion)
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_placeholders() {
+        assert_eq!(Some("jemandem".to_string()), Placeholder::expand("jdm.", 0, &Language::DE));
+        assert_eq!(Some("jemanden/etwas".to_string()), Placeholder::expand("jdn./etw.", 0, &Language::DE));
+        assert_eq!(Some("something".to_string()), Placeholder::expand("sth.", 2, &Language::EN));
+        assert_eq!(None, Placeholder::expand("sth.", 2, &Language::DE));
+        assert_eq!(None, Placeholder::expand("anrufen", 1, &Language::DE));
+
+        assert_eq!(Some(Case::Dative), Placeholder::parse_word("jdm.", 0, &Language::DE).unwrap()[0].case);
+
+        // Reflexive pronouns in front of the verb only
+        assert!(Placeholder::parse_word("sich", 0, &Language::DE).is_some());
+        assert!(Placeholder::parse_word("sich", 1, &Language::DE).is_none());
+    }
+
+    #[test]
+    fn test_placeholder_words() {
+        let dict = Dict::create("tests/database/test_database_placeholders.txt").unwrap();
+        let entry = &dict.entries[0];
+
+        assert_eq!(vec!["jdn."], entry.left_word.placeholders());
+        assert_eq!(vec!["sb."], entry.right_word.placeholders());
+        assert_eq!(1, entry.left_word.word_count);
+        assert_eq!("jemanden anrufen", entry.left_word.with_expanded_placeholders());
+        assert_eq!("to call somebody", entry.right_word.with_expanded_placeholders());
+
+        // The placeholders are not indexed, so `anrufen` matches both entries exactly
+        let mut query = dict.query("anrufen");
+        query.exact();
+        assert_eq!(vec![entry, &dict.entries[2]], query.execute().unwrap().entries().to_vec());
+
+        // The full form with the placeholders matches as well
+        let query_entries = |term: &str, query_type: QueryType| {
+            let mut query = dict.query(term);
+            query.set_type(query_type);
+            query.execute().unwrap().entries().to_vec()
+        };
+
+        assert_eq!(vec![entry], query_entries("jdn. anrufen", QueryType::Exact));
+        assert_eq!(vec![entry], query_entries("JDN. ANRUFEN", QueryType::Exact));
+        assert_eq!(vec![entry], query_entries("to call sb.", QueryType::Exact));
+        assert_eq!(vec![entry], query_entries("sb.", QueryType::Word));
+        assert_eq!(vec![entry], query_entries("jdn.", QueryType::Word));
+        assert!(query_entries("jdm. anrufen", QueryType::Exact).is_empty());
+
+        let mut query = dict.query("jdn. anrufen");
+        query.exact().case_sensitive(true);
+        assert_eq!(vec![entry], query.execute().unwrap().entries().to_vec());
+
+        // The placeholders are highlighted, if the full form matched
+        let result = dict.query("sb.").execute().unwrap();
+        let right_word = entry.right_word.to_string();
+        let spans: Vec<&str> = result.matches()[0].right_spans().iter().map(|span| &right_word[span.clone()]).collect();
+        assert_eq!(vec!["sb."], spans);
+
+        // The full form is an exact match like the word without the placeholders
+        let full_form = dict.query("to call sb.").execute().unwrap();
+        let without_placeholders = dict.query("to call").execute().unwrap();
+        assert_eq!(without_placeholders.relevances(), full_form.relevances());
+        assert!(dict.query("call").execute().unwrap().relevances() < full_form.relevances());
+
+        let entry = dict.entries.iter().find(|entry| entry.left_word.plain_word() == "beeilen").unwrap();
+        assert_eq!(vec![entry], query_entries("sich beeilen", QueryType::Exact));
+        assert_eq!(vec!["sich"], entry.left_word.placeholders());
+        assert!(entry.right_word.placeholders().is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+//! Random sampling of entries, e.g. for vocabulary training.
+//!
+//! The sampling is deterministic: the same seed, filter and database always result in the same entries.
+
+use super::*;
+
+impl Dict {
+    /// Returns `n` distinct random entries, which match the `filter`.
+    ///
+    /// Returns less than `n` entries, if less entries match the filter.
+    pub fn random_entries(&self, n: usize, seed: u64, filter: &EntryFilter) -> Vec<&DictEntry> {
+        let mut candidates: Vec<&DictEntry> = self.entries.iter()
+            .filter(|entry| filter.matches(entry))
+            .collect();
+
+        let mut rng = SplitMix64::new(seed);
+        let n = ::std::cmp::min(n, candidates.len());
+
+        // Partial Fisher-Yates shuffle of the first n entries
+        for i in 0..n {
+            let j = i + rng.next_below(candidates.len() - i);
+            candidates.swap(i, j);
+        }
+
+        candidates.truncate(n);
+        candidates
+    }
+
+    /// Returns the entry of the day `day`, which matches the `filter`.
+    ///
+    /// `day` is any consecutive numbering of the calendar days, e.g. the days since the common era.
+    /// The entry is stable for every day, as long as the database and the filter are not changed.
+    /// Returns `None`, if no entry matches the filter.
+    pub fn word_of_the_day(&self, day: u64, filter: &EntryFilter) -> Option<&DictEntry> {
+        self.random_entries(1, day, filter).pop()
+    }
+}
+
+/// Small and fast pseudo random number generator (see [SplitMix64](http://xoshiro.di.unimi.it/splitmix64.c)).
+///
+/// Not suitable for cryptographic purposes.
+#[derive(Debug)]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 {
+            state: seed,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`. The bias of the modulo is negligible for the size of a `Dict`.
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_mix_64() {
+        // Reference values of the C implementation with seed 1234567
+        let mut rng = SplitMix64::new(1_234_567);
+
+        assert_eq!(6_457_827_717_110_365_317, rng.next_u64());
+        assert_eq!(3_203_168_211_198_807_973, rng.next_u64());
+        assert_eq!(9_817_491_932_198_370_423, rng.next_u64());
+    }
+
+    #[test]
+    fn test_random_entries() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        let filter = EntryFilter::default();
+
+        let entries = dict.random_entries(5, 42, &filter);
+        assert_eq!(5, entries.len());
+        assert_eq!(entries, dict.random_entries(5, 42, &filter));
+
+        let mut distinct: Vec<_> = entries.iter().map(|&entry| entry as *const DictEntry).collect();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(entries.len(), distinct.len());
+
+        assert_eq!(dict.entries.len(), dict.random_entries(1000, 42, &filter).len());
+    }
+
+    #[test]
+    fn test_word_of_the_day() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        let filter = EntryFilter {
+            any_gender: true,
+            ..Default::default()
+        };
+
+        let entry = dict.word_of_the_day(736_000, &filter).unwrap();
+        assert_eq!(Some(entry), dict.word_of_the_day(736_000, &filter));
+        assert!(entry.left_word.has_any_gender());
+
+        let filter = EntryFilter {
+            gender: Some(Gender::Masculine),
+            ..Default::default()
+        };
+        assert_eq!(None, dict.word_of_the_day(736_000, &filter));
+    }
+}
@@ -0,0 +1,146 @@
+//! Regional varieties of words, e.g. `[Br.]`, `[Am.]` or `[österr.]`.
+//!
+//! The regions are read from the comments of the words.
+//! A query can filter (`EntryFilter::region`) or rank (`RelevanceModel::preferred_region`)
+//! the entries by a region.
+
+use super::*;
+
+/// Regional variety of a `DictWord`, as labeled by dict.cc.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Region {
+    // English
+    British,
+    American,
+    Australian,
+    Canadian,
+    Irish,
+    Scottish,
+    NewZealand,
+    // German
+    Austrian,
+    Swiss,
+    SouthernGerman,
+    NorthernGerman,
+    EasternGerman,
+}
+
+impl Region {
+    /// The label used by dict.cc, e.g. `Br.`
+    pub fn label(self) -> &'static str {
+        use self::Region::*;
+
+        match self {
+            British => "Br.",
+            American => "Am.",
+            Australian => "Aus.",
+            Canadian => "Can.",
+            Irish => "Irish",
+            Scottish => "Scot.",
+            NewZealand => "NZ",
+            Austrian => "österr.",
+            Swiss => "schweiz.",
+            SouthernGerman => "südd.",
+            NorthernGerman => "nordd.",
+            EasternGerman => "ostd.",
+        }
+    }
+
+    /// The language, of which the region is a variety.
+    pub fn language(self) -> Language {
+        use self::Region::*;
+
+        match self {
+            British | American | Australian | Canadian | Irish | Scottish | NewZealand => Language::EN,
+            Austrian | Swiss | SouthernGerman | NorthernGerman | EasternGerman => Language::DE,
+        }
+    }
+
+    /// Whether `word` may be used in this region.
+    ///
+    /// Words without a region of the same language are common to all regions.
+    pub fn is_compatible(self, word: &DictWord) -> bool {
+        let language = self.language();
+
+        word.regions().contains(&self)
+            || word.regions().iter().all(|region| region.language() != language)
+    }
+
+    /// Parses the regions of a comment, e.g. `Br.` of `[Br.]` or `Am., Can.` of `[Am., Can.]`.
+    ///
+    /// Returns nothing, if the comment contains anything else than region labels.
+    pub(crate) fn parse_comment(comment: &str) -> Vec<Region> {
+        let mut regions = vec![];
+
+        for segment in comment.split([',', '/']).map(|segment| segment.trim()).filter(|segment| !segment.is_empty()) {
+            // Multi-word labels, e.g. `New Zealand`, are matched before the segment is split into words
+            if let Ok(region) = segment.split_whitespace().collect::<Vec<_>>().join(" ").parse() {
+                regions.push(region);
+                continue;
+            }
+
+            for label in segment.split_whitespace() {
+                match label.parse() {
+                    Ok(region) => regions.push(region),
+                    Err(_) => return vec![],
+                }
+            }
+        }
+
+        regions
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Region {
+    type Err = DictError;
+
+    /// Performs the fault-tolerant conversion from a label (`Br.`, `br`) or an English name (`british`) into a Region
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Region::*;
+
+        Ok(match s.trim_end_matches('.').to_lowercase().as_str() {
+            "br" | "brit" | "british" | "uk" => British,
+            "am" | "american" | "us" => American,
+            "aus" | "austr" | "australian" => Australian,
+            "can" | "canadian" => Canadian,
+            "irish" | "ir" => Irish,
+            "scot" | "scottish" => Scottish,
+            "nz" | "new zealand" => NewZealand,
+            "österr" | "oesterr" | "austrian" | "at" => Austrian,
+            "schweiz" | "swiss" | "ch" => Swiss,
+            "südd" | "suedd" | "southern german" => SouthernGerman,
+            "nordd" | "northern german" => NorthernGerman,
+            "ostd" | "eastern german" => EasternGerman,
+            _ => Err(DictError::UnknownRegion { region: s.to_string(), backtrace: Backtrace::new() })?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_regions() {
+        assert_eq!(vec![Region::British], Region::parse_comment("Br."));
+        assert_eq!(vec![Region::American, Region::Canadian], Region::parse_comment("Am., Can."));
+        assert_eq!(vec![Region::Austrian, Region::SouthernGerman], Region::parse_comment("österr. / südd."));
+        assert_eq!(vec![Region::NewZealand], Region::parse_comment("New Zealand"));
+        assert_eq!(vec![Region::Australian, Region::NewZealand], Region::parse_comment("Aus., New Zealand"));
+        assert_eq!(vec![Region::British, Region::Irish], Region::parse_comment("Br. Irish"));
+        assert!(Region::parse_comment("New York").is_empty());
+        assert!(Region::parse_comment("Br. coll.").is_empty());
+        assert!(Region::parse_comment("").is_empty());
+
+        assert_eq!(Region::Swiss, "CH".parse().unwrap());
+        assert_eq!(Region::American, "us".parse().unwrap());
+        assert!("foo".parse::<Region>().is_err());
+    }
+}
@@ -0,0 +1,116 @@
+//! Register and style labels of words, e.g. `[coll.]`, `[vulg.]` or `[fig.]`.
+//!
+//! The registers are read from the comments of the words, both the English labels
+//! and their German equivalents (`[ugs.]`, `[übtr.]`, `[veraltet]`, ...).
+
+use super::*;
+
+/// Register or style of a `DictWord`, as labeled by dict.cc.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Register {
+    Colloquial,
+    Slang,
+    Vulgar,
+    Pejorative,
+    Figurative,
+    Euphemistic,
+    Humorous,
+    Formal,
+    Poetic,
+    Rare,
+    Dated,
+    Archaic,
+    Historical,
+}
+
+impl Register {
+    /// The English label used by dict.cc, e.g. `coll.`
+    pub fn label(self) -> &'static str {
+        use self::Register::*;
+
+        match self {
+            Colloquial => "coll.",
+            Slang => "sl.",
+            Vulgar => "vulg.",
+            Pejorative => "pej.",
+            Figurative => "fig.",
+            Euphemistic => "euphem.",
+            Humorous => "hum.",
+            Formal => "formal",
+            Poetic => "poet.",
+            Rare => "rare",
+            Dated => "dated",
+            Archaic => "archaic",
+            Historical => "hist.",
+        }
+    }
+
+    /// Whether words of this register are offensive, i.e. vulgar or pejorative.
+    pub fn is_offensive(self) -> bool {
+        self == Register::Vulgar || self == Register::Pejorative
+    }
+
+    /// Parses the registers of a comment, e.g. `coll.` of `[coll.]` or `coll., pej.` of `[coll., pej.]`.
+    ///
+    /// Returns nothing, if the comment contains anything else than register labels.
+    pub(crate) fn parse_comment(comment: &str) -> Vec<Register> {
+        comment.split([',', '/', ';'])
+            .map(|label| label.trim())
+            .filter(|label| !label.is_empty())
+            .map(|label| label.parse())
+            .collect::<DictResult<Vec<Register>>>()
+            .unwrap_or_default()
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Register {
+    type Err = DictError;
+
+    /// Performs the fault-tolerant conversion from an English or German label (`coll.`, `ugs`)
+    /// or an English name (`colloquial`) into a Register
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Register::*;
+
+        Ok(match s.trim_end_matches('.').to_lowercase().as_str() {
+            "coll" | "colloquial" | "ugs" | "umgangssprachlich" => Colloquial,
+            "sl" | "slang" | "salopp" | "jargon" => Slang,
+            "vulg" | "vulgar" | "derb" => Vulgar,
+            "pej" | "pejorative" | "pejorativ" | "abwertend" | "abw" => Pejorative,
+            "fig" | "figurative" | "übtr" | "übertragen" => Figurative,
+            "euphem" | "euphemistic" | "euphemistisch" => Euphemistic,
+            "hum" | "humorous" | "scherzh" | "scherzhaft" => Humorous,
+            "formal" | "geh" | "gehoben" | "formell" => Formal,
+            "poet" | "poetic" | "dichter" => Poetic,
+            "rare" | "selten" => Rare,
+            "dated" | "veraltend" => Dated,
+            "archaic" | "obs" | "obsolete" | "veraltet" => Archaic,
+            "hist" | "historical" | "historisch" => Historical,
+            _ => Err(DictError::UnknownRegister { register: s.to_string(), backtrace: Backtrace::new() })?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_registers() {
+        assert_eq!(vec![Register::Colloquial], Register::parse_comment("coll."));
+        assert_eq!(vec![Register::Colloquial, Register::Pejorative], Register::parse_comment("ugs., pej."));
+        assert_eq!(vec![Register::Figurative], Register::parse_comment("übtr."));
+        assert_eq!(vec![Register::Archaic], Register::parse_comment("veraltet"));
+        assert!(Register::parse_comment("coll. for a car").is_empty());
+        assert!(Register::parse_comment("").is_empty());
+
+        assert_eq!(Register::Vulgar, "Vulgar".parse().unwrap());
+        assert!("foo".parse::<Register>().is_err());
+    }
+}
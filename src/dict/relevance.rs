@@ -0,0 +1,227 @@
+//! Relevance ranking of `DictQueryResult` entries.
+//!
+//! Every entry found by a `DictQuery` is scored by a `RelevanceModel`.
+//! The score is composed of:
+//!
+//! 1. How well the query term matches the word (`MatchQuality`)
+//! 2. A penalty for every word in the entry, which is not part of the query term
+//! 3. A penalty for every comment of the matched word
+//! 4. A weight for the side of the entry the query term matched on
+//! 5. A bonus for frequently used translations, if frequency data is provided
+//! 6. A bonus for words of the preferred region and a penalty for words of other regions of its language
+
+use super::*;
+
+use std::cmp::max;
+use std::collections::HashMap;
+
+/// How well the query term matches a word.
+///
+/// Ordered from the worst to the best match.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum MatchQuality {
+    /// The query term is only a part of the word.
+    ///
+    /// `Wort` for `langes Wort`
+    Word,
+    /// The query term equals the word without its optional parts.
+    ///
+    /// `Wort` for `(optionales) Wort`
+    IgnoringOptionalParts,
+    /// The query term equals the word, with or without its placeholders.
+    ///
+    /// `Wort` for `Wort {n}`, `anrufen` and `jdn. anrufen` for `jdn. anrufen`
+    Exact,
+}
+
+impl MatchQuality {
+    fn of(word: &DictWord, query_term: &str) -> Self {
+        let query_term = query_term.to_lowercase();
+
+        if word.indexed_word == query_term || word.indexed_full_word.as_deref() == Some(query_term.as_str()) {
+            MatchQuality::Exact
+        } else if word.plain_word().to_lowercase() == query_term {
+            MatchQuality::IgnoringOptionalParts
+        } else {
+            MatchQuality::Word
+        }
+    }
+}
+
+/// The relevance of an entry in a `DictQueryResult`.
+///
+/// A higher score means a more relevant entry.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Relevance {
+    score: i32,
+}
+
+impl Relevance {
+    /// The score of the entry computed by the `RelevanceModel`.
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+}
+
+/// Weights used to compute the `Relevance` of an entry.
+///
+/// The default weights rank by `MatchQuality` first,
+/// the penalties only decide between entries of the same quality.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RelevanceModel {
+    /// Score of a `MatchQuality::Exact` match.
+    pub exact_match: i32,
+    /// Score of a `MatchQuality::IgnoringOptionalParts` match.
+    pub ignoring_optional_parts_match: i32,
+    /// Score of a `MatchQuality::Word` match.
+    pub word_match: i32,
+    /// Subtracted for every word of the matched word, which is not in the query term.
+    pub extra_word_penalty: i32,
+    /// Subtracted for every comment of the matched word.
+    pub comment_penalty: i32,
+    /// Added if the query term matched the left word.
+    pub left_side_weight: i32,
+    /// Added if the query term matched the right word.
+    pub right_side_weight: i32,
+    /// Multiplied with the binary logarithm of the translation's frequency.
+    pub frequency_weight: i32,
+    /// User supplied frequencies of words, e.g. from a corpus.
+    ///
+    /// The keys are the plain words in lowercase, see `DictWord::plain_word`.
+    pub frequencies: HashMap<String, u32>,
+    /// The regional variety, whose words are preferred, e.g. `Region::British`.
+    pub preferred_region: Option<Region>,
+    /// Added for every word of the preferred region and subtracted for every word,
+    /// which is restricted to other regions of the same language.
+    pub region_weight: i32,
+}
+
+impl Default for RelevanceModel {
+    fn default() -> Self {
+        RelevanceModel {
+            exact_match: 3000,
+            ignoring_optional_parts_match: 2000,
+            word_match: 1000,
+            extra_word_penalty: 20,
+            comment_penalty: 5,
+            left_side_weight: 0,
+            right_side_weight: 0,
+            frequency_weight: 10,
+            frequencies: HashMap::new(),
+            preferred_region: None,
+            region_weight: 50,
+        }
+    }
+}
+
+impl RelevanceModel {
+    /// Creates the default model using the given word frequencies.
+    pub fn with_frequencies(frequencies: HashMap<String, u32>) -> Self {
+        RelevanceModel {
+            frequencies,
+            ..Default::default()
+        }
+    }
+
+    /// Computes the relevance of `entry`, which was found by `query_term` on `matched_side`.
+    pub fn score(&self, entry: &DictEntry, matched_side: EntrySide, query_term: &str) -> Relevance {
+        let score = match matched_side {
+            EntrySide::Left => self.score_side(&entry.left_word, &entry.right_word, self.left_side_weight, query_term),
+            EntrySide::Right => self.score_side(&entry.right_word, &entry.left_word, self.right_side_weight, query_term),
+            EntrySide::Both => max(
+                self.score_side(&entry.left_word, &entry.right_word, self.left_side_weight, query_term),
+                self.score_side(&entry.right_word, &entry.left_word, self.right_side_weight, query_term),
+            ),
+        };
+
+        Relevance { score: score + self.region_score(&entry.left_word) + self.region_score(&entry.right_word) }
+    }
+
+    fn score_side(&self, matched_word: &DictWord, translation: &DictWord, side_weight: i32, query_term: &str) -> i32 {
+        let quality_score = match MatchQuality::of(matched_word, query_term) {
+            MatchQuality::Exact => self.exact_match,
+            MatchQuality::IgnoringOptionalParts => self.ignoring_optional_parts_match,
+            MatchQuality::Word => self.word_match,
+        };
+
+        let query_word_count = query_term.split_whitespace().count() as i32;
+        let extra_words = max(i32::from(matched_word.word_count) - query_word_count, 0);
+        let comments = matched_word.comments().len() as i32;
+
+        quality_score
+            - extra_words * self.extra_word_penalty
+            - comments * self.comment_penalty
+            + side_weight
+            + self.frequency_score(translation)
+    }
+
+    fn region_score(&self, word: &DictWord) -> i32 {
+        match self.preferred_region {
+            Some(region) if word.regions().contains(&region) => self.region_weight,
+            Some(region) if !region.is_compatible(word) => -self.region_weight,
+            _ => 0,
+        }
+    }
+
+    fn frequency_score(&self, translation: &DictWord) -> i32 {
+        if self.frequencies.is_empty() {
+            return 0;
+        }
+
+        let frequency = self.frequencies
+            .get(&translation.plain_word().to_lowercase())
+            .cloned()
+            .unwrap_or(0);
+
+        // Binary logarithm of (frequency + 1), so that rare words still get a small bonus
+        let log_frequency = (31 - frequency.saturating_add(1).leading_zeros()) as i32;
+
+        log_frequency * self.frequency_weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_by_relevance() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+
+        let result = dict.query("foo").execute().unwrap().sorted_by_relevance();
+        let left_words: Vec<_> = result.entries().iter().map(|entry| entry.left_word.plain_word()).collect();
+
+        // The exact headword ranks above the longer compounds, which are ranked by their word count
+        assert_eq!("foo", left_words[0]);
+        assert_eq!("foo bar baz qux", left_words[left_words.len() - 1]);
+
+        let scores: Vec<_> = result.relevances().iter().map(|relevance| relevance.score()).collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn test_match_quality() {
+        let dict = Dict::create("tests/database/test_database.txt").unwrap();
+        // `(optionales) Wort {f} [Kommentar] <Akronym, anderes Akronym>`
+        let word = &dict.entries[1].left_word;
+
+        assert_eq!(MatchQuality::Exact, MatchQuality::of(word, "optionales wort"));
+        assert_eq!(MatchQuality::IgnoringOptionalParts, MatchQuality::of(word, "Wort"));
+        assert_eq!(MatchQuality::Word, MatchQuality::of(word, "optionales"));
+    }
+
+    #[test]
+    fn test_preferred_region() {
+        let dict = Dict::create("tests/database/test_database_regions.txt").unwrap();
+        let ranked = |region: Region| -> Vec<String> {
+            let mut query = dict.query("lastwagen");
+            query.prefer_region(region);
+            query.execute().unwrap().sorted_by_relevance().entries().iter()
+                .map(|entry| entry.right_word.plain_word())
+                .collect()
+        };
+
+        assert_eq!(vec!["lorry", "truck"], ranked(Region::British));
+        assert_eq!(vec!["truck", "lorry"], ranked(Region::American));
+    }
+}
@@ -0,0 +1,233 @@
+//! Statistics about the content of a `Dict`.
+//!
+//! `DictStats` implements Display using formatted tables
+//! and `Serialize`, e.g. to export the statistics as JSON.
+
+use super::*;
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Problems found while reading the database in `Dict::create`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Serialize)]
+pub struct DictLoadStats {
+    /// The number of records, which were dropped, because they have less than three columns.
+    pub incomplete_records: usize,
+    /// The number of records, which were dropped, because they have more than four columns.
+    pub too_many_columns: usize,
+    /// The number of words, whose bracket syntax could not be parsed.
+    /// These words are used as they are, without optional parts, comments etc.
+    pub fallback_words: usize,
+    /// The number of entries, which were dropped, because of an invalid word class.
+    pub invalid_entries: usize,
+}
+
+/// Statistics of one side (language) of a `Dict`.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct DictSideStats {
+    /// The language of this side, e.g. `German`.
+    pub language: String,
+    /// The number of words per gender, e.g. `f`. Alternatives of a tag are counted separately.
+    pub genders: BTreeMap<String, usize>,
+    /// The number of words per grammatical number, e.g. `pl`.
+    pub numbers: BTreeMap<String, usize>,
+    /// The number of words per word count.
+    pub word_counts: BTreeMap<u8, usize>,
+    /// The number of words with comments.
+    pub words_with_comments: usize,
+    /// The number of words with acronyms.
+    pub words_with_acronyms: usize,
+    /// The most frequent words (tokens of the indexed words) with their frequency, in descending frequency.
+    pub top_tokens: Vec<(String, usize)>,
+}
+
+impl DictSideStats {
+    fn new(words: &[&DictWord], language: &Language, max_top_tokens: usize) -> Self {
+        let mut genders = BTreeMap::new();
+        let mut numbers = BTreeMap::new();
+        let mut word_counts = BTreeMap::new();
+        let mut words_with_comments = 0;
+        let mut words_with_acronyms = 0;
+        let mut token_frequencies: HashMap<&str, usize> = HashMap::new();
+
+        for word in words {
+            for tag in word.typed_genders() {
+                for alternative in tag.alternatives() {
+                    if let Some(gender) = alternative.gender {
+                        *genders.entry(gender.to_string()).or_insert(0) += 1;
+                    }
+
+                    if let Some(number) = alternative.number {
+                        *numbers.entry(number.to_string()).or_insert(0) += 1;
+                    }
+                }
+            }
+
+            *word_counts.entry(word.word_count).or_insert(0) += 1;
+
+            if !word.indexed_comments.is_empty() {
+                words_with_comments += 1;
+            }
+
+            if !word.indexed_acronyms.is_empty() {
+                words_with_acronyms += 1;
+            }
+
+            for token in word.indexed_word.split(|c: char| c.is_whitespace() || c == '-').filter(|token| !token.is_empty()) {
+                *token_frequencies.entry(token).or_insert(0) += 1;
+            }
+        }
+
+        let mut top_tokens: Vec<(String, usize)> = token_frequencies.into_iter()
+            .map(|(token, frequency)| (token.to_string(), frequency))
+            .collect();
+
+        top_tokens.sort_by(|&(ref left_token, left_frequency), &(ref right_token, right_frequency)| {
+            right_frequency.cmp(&left_frequency)
+                .then_with(|| left_token.cmp(right_token))
+        });
+        top_tokens.truncate(max_top_tokens);
+
+        DictSideStats {
+            language: language.to_string(),
+            genders,
+            numbers,
+            word_counts,
+            words_with_comments,
+            words_with_acronyms,
+            top_tokens,
+        }
+    }
+}
+
+/// Statistics about the content of a `Dict`. Created by `Dict::stats`.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct DictStats {
+    /// The number of entries.
+    pub entry_count: usize,
+    /// The number of entries per word class. An entry may have multiple word classes.
+    pub word_classes: BTreeMap<String, usize>,
+    /// The number of entries without a word class.
+    pub entries_without_word_class: usize,
+    /// The statistics of the left words.
+    pub left: DictSideStats,
+    /// The statistics of the right words.
+    pub right: DictSideStats,
+    /// Problems found while reading the database.
+    pub load: DictLoadStats,
+}
+
+impl Dict {
+    /// Computes statistics about the entries.
+    ///
+    /// Lists the `max_top_tokens` most frequent words of each side.
+    pub fn stats(&self, max_top_tokens: usize) -> DictStats {
+        let mut word_classes = BTreeMap::new();
+        let mut entries_without_word_class = 0;
+
+        for entry in &self.entries {
+            if entry.word_classes.is_empty() {
+                entries_without_word_class += 1;
+            }
+
+            for word_class in &entry.word_classes {
+                *word_classes.entry(word_class.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        let left_words: Vec<_> = self.entries.iter().map(|entry| &entry.left_word).collect();
+        let right_words: Vec<_> = self.entries.iter().map(|entry| &entry.right_word).collect();
+
+        DictStats {
+            entry_count: self.entries.len(),
+            word_classes,
+            entries_without_word_class,
+            left: DictSideStats::new(&left_words, &self.languages.left_language, max_top_tokens),
+            right: DictSideStats::new(&right_words, &self.languages.right_language, max_top_tokens),
+            load: self.load_stats,
+        }
+    }
+}
+
+impl Display for DictStats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR;
+        use prettytable::Table;
+
+        fn format_counts<K: Display>(counts: &BTreeMap<K, usize>) -> String {
+            counts.iter()
+                .map(|(key, count)| format!("{}: {}", key, count))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        fn format_top_tokens(top_tokens: &[(String, usize)]) -> String {
+            top_tokens.iter()
+                .map(|&(ref token, frequency)| format!("{}: {}", token, frequency))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        let mut overview = Table::init(vec![
+            row!["Entries", self.entry_count],
+            row!["Word classes", format_counts(&self.word_classes)],
+            row!["Without word class", self.entries_without_word_class],
+            row!["Incomplete records (dropped)", self.load.incomplete_records],
+            row!["Records with too many columns (dropped)", self.load.too_many_columns],
+            row!["Invalid entries (dropped)", self.load.invalid_entries],
+            row!["Unparsable words (fallback)", self.load.fallback_words],
+        ]);
+
+        overview.set_format(*FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        let mut sides = Table::init(vec![
+            row!["", self.left.language, self.right.language],
+            row!["Genders", format_counts(&self.left.genders), format_counts(&self.right.genders)],
+            row!["Numbers", format_counts(&self.left.numbers), format_counts(&self.right.numbers)],
+            row!["Word counts", format_counts(&self.left.word_counts), format_counts(&self.right.word_counts)],
+            row!["With comments", self.left.words_with_comments, self.right.words_with_comments],
+            row!["With acronyms", self.left.words_with_acronyms, self.right.words_with_acronyms],
+            row!["Top words", format_top_tokens(&self.left.top_tokens), format_top_tokens(&self.right.top_tokens)],
+        ]);
+
+        sides.set_format(*FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        write!(f, "{}\n{}", overview, sides)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let dict = Dict::create("tests/database/test_database_stats.txt").unwrap();
+        let stats = dict.stats(3);
+
+        assert_eq!(8, stats.entry_count);
+        assert_eq!(1, stats.entries_without_word_class);
+
+        let word_classes: Vec<(&str, usize)> = stats.word_classes.iter().map(|(class, &count)| (class.as_str(), count)).collect();
+        assert_eq!(vec![("adj", 1), ("noun", 4), ("verb", 2)], word_classes);
+
+        assert_eq!("German", stats.left.language);
+        assert_eq!("English", stats.right.language);
+
+        let genders: Vec<(&str, usize)> = stats.left.genders.iter().map(|(gender, &count)| (gender.as_str(), count)).collect();
+        assert_eq!(vec![("f", 1), ("m", 1), ("n", 1)], genders);
+        assert_eq!(Some(&1), stats.left.numbers.get("pl"));
+        assert!(stats.right.genders.is_empty());
+
+        assert_eq!(vec![(1, 6), (2, 2)], stats.left.word_counts.into_iter().collect::<Vec<_>>());
+        assert_eq!(0, stats.left.words_with_comments);
+        assert_eq!(1, stats.right.words_with_comments);
+        assert_eq!(1, stats.right.words_with_acronyms);
+
+        // Tokens with the same frequency are sorted alphabetically
+        let top_tokens: Vec<(&str, usize)> = stats.left.top_tokens.iter().map(|&(ref token, count)| (token.as_str(), count)).collect();
+        assert_eq!(vec![("laufen", 2), ("schnell", 2), ("haus", 1)], top_tokens);
+
+        assert_eq!(1, stats.load.incomplete_records);
+        assert_eq!(0, stats.load.too_many_columns);
+    }
+}
@@ -0,0 +1,201 @@
+//! Subject areas of entries, e.g. `[med.]` or `[tech.]`.
+//!
+//! The subjects are read from the optional fourth column of the database
+//! and from the comments of the words, which consist only of subject labels.
+
+use super::*;
+
+/// Subject area of a `DictEntry`, as labeled by dict.cc.
+#[allow(missing_docs)]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Subject {
+    Agriculture,
+    Anatomy,
+    Architecture,
+    Art,
+    Astronomy,
+    Automotive,
+    Aviation,
+    Biology,
+    Botany,
+    Chemistry,
+    Computing,
+    Construction,
+    Cooking,
+    Economics,
+    Education,
+    Electrical,
+    Finance,
+    Geography,
+    Geology,
+    Law,
+    Linguistics,
+    Literature,
+    Mathematics,
+    Medicine,
+    Military,
+    Music,
+    Nautical,
+    Pharmacy,
+    Philosophy,
+    Physics,
+    Politics,
+    Psychology,
+    Religion,
+    Sports,
+    Technology,
+    Telecommunications,
+    Zoology,
+    /// A subject label of the subject column, which is not known, without brackets
+    Other(String),
+}
+
+impl Subject {
+    /// The label used by dict.cc, e.g. `med.`
+    pub fn label(&self) -> &str {
+        use self::Subject::*;
+
+        match *self {
+            Agriculture => "agr.",
+            Anatomy => "anat.",
+            Architecture => "archi.",
+            Art => "art",
+            Astronomy => "astron.",
+            Automotive => "automot.",
+            Aviation => "aviat.",
+            Biology => "biol.",
+            Botany => "bot.",
+            Chemistry => "chem.",
+            Computing => "comp.",
+            Construction => "constr.",
+            Cooking => "gastr.",
+            Economics => "econ.",
+            Education => "educ.",
+            Electrical => "electr.",
+            Finance => "fin.",
+            Geography => "geogr.",
+            Geology => "geol.",
+            Law => "law",
+            Linguistics => "ling.",
+            Literature => "lit.",
+            Mathematics => "math.",
+            Medicine => "med.",
+            Military => "mil.",
+            Music => "mus.",
+            Nautical => "naut.",
+            Pharmacy => "pharm.",
+            Philosophy => "philos.",
+            Physics => "phys.",
+            Politics => "pol.",
+            Psychology => "psych.",
+            Religion => "relig.",
+            Sports => "sports",
+            Technology => "tech.",
+            Telecommunications => "telecom.",
+            Zoology => "zool.",
+            Other(ref label) => label,
+        }
+    }
+
+    /// Parses the subjects of the subject column, e.g. `[med.] [pharm.]` or `med. pharm.`
+    ///
+    /// Unknown labels are kept as `Subject::Other`.
+    pub(crate) fn parse_column(column: &str) -> Vec<Subject> {
+        split_labels(column).into_iter()
+            .map(|label| label.parse().unwrap_or_else(|_| Subject::Other(label.to_string())))
+            .collect()
+    }
+
+    /// Parses the subjects of a comment, e.g. `med.` of `[med.]`.
+    ///
+    /// Returns nothing, if the comment contains anything else than known subject labels.
+    pub(crate) fn parse_comment(comment: &str) -> Vec<Subject> {
+        split_labels(comment).into_iter()
+            .map(|label| label.parse())
+            .collect::<DictResult<Vec<Subject>>>()
+            .unwrap_or_default()
+    }
+}
+
+impl Display for Subject {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Subject {
+    type Err = DictError;
+
+    /// Performs the fault-tolerant conversion from a label (`med.`, `[med.]`, `med`)
+    /// or an English name (`medicine`) into a known Subject
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Subject::*;
+
+        Ok(match s.trim_matches(|c| c == '[' || c == ']').trim_end_matches('.').to_lowercase().as_str() {
+            "agr" | "agriculture" => Agriculture,
+            "anat" | "anatomy" => Anatomy,
+            "archi" | "architecture" => Architecture,
+            "art" => Art,
+            "astron" | "astronomy" => Astronomy,
+            "automot" | "automotive" => Automotive,
+            "aviat" | "aviation" => Aviation,
+            "biol" | "biology" => Biology,
+            "bot" | "botany" => Botany,
+            "chem" | "chemistry" => Chemistry,
+            "comp" | "computing" | "computer" => Computing,
+            "constr" | "construction" => Construction,
+            "gastr" | "cook" | "cooking" => Cooking,
+            "econ" | "economics" | "economy" => Economics,
+            "educ" | "education" => Education,
+            "electr" | "electrical" | "electronics" => Electrical,
+            "fin" | "finance" => Finance,
+            "geogr" | "geography" => Geography,
+            "geol" | "geology" => Geology,
+            "law" | "jur" | "legal" => Law,
+            "ling" | "linguistics" => Linguistics,
+            "lit" | "literature" => Literature,
+            "math" | "mathematics" => Mathematics,
+            "med" | "medicine" | "medical" => Medicine,
+            "mil" | "military" => Military,
+            "mus" | "music" => Music,
+            "naut" | "nautical" => Nautical,
+            "pharm" | "pharmacy" => Pharmacy,
+            "philos" | "philosophy" => Philosophy,
+            "phys" | "physics" => Physics,
+            "pol" | "politics" => Politics,
+            "psych" | "psychology" => Psychology,
+            "relig" | "religion" => Religion,
+            "sports" | "sport" => Sports,
+            "tech" | "technology" | "technical" => Technology,
+            "telecom" | "telecommunications" => Telecommunications,
+            "zool" | "zoology" => Zoology,
+            _ => Err(DictError::UnknownSubject { subject: s.to_string(), backtrace: Backtrace::new() })?
+        })
+    }
+}
+
+/// Splits a list of subject labels, e.g. `[med.] [pharm.]`, `med., pharm.`
+fn split_labels(s: &str) -> Vec<&str> {
+    s.split(|c: char| c == '[' || c == ']' || c == ',' || c == ';' || c.is_whitespace())
+        .filter(|label| !label.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_subjects() {
+        assert_eq!(vec![Subject::Medicine, Subject::Pharmacy], Subject::parse_column("[med.] [pharm.]"));
+        assert_eq!(vec![Subject::Technology, Subject::Other("RadioTV".to_string())], Subject::parse_column("tech. RadioTV"));
+        assert!(Subject::parse_column("").is_empty());
+
+        assert_eq!(vec![Subject::Law], Subject::parse_comment("law"));
+        assert_eq!(vec![Subject::Medicine, Subject::Biology], Subject::parse_comment("med., biol."));
+        assert!(Subject::parse_comment("tech. jargon").is_empty());
+
+        assert_eq!(Subject::Medicine, "Medical".parse().unwrap());
+        assert!("foo".parse::<Subject>().is_err());
+    }
+}
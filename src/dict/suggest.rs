@@ -0,0 +1,133 @@
+//! "Did you mean" suggestions for queries without results.
+//!
+//! The suggestions are the headwords of the `Dict`, whose normalized spelling
+//! (lowercase and without diacritics) has the smallest edit distance to the query term.
+
+use super::*;
+
+use dict::normalize::normalize_spelling;
+use std::cmp::min;
+use std::collections::HashSet;
+
+/// A headword of the `Dict`, which is similar to a query term.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Suggestion {
+    /// The suggested headword, which can be used as a new query term.
+    pub word: String,
+    /// The language of the headword.
+    pub language: Language,
+    /// The edit distance between the normalized spellings of the query term and the headword.
+    pub distance: usize,
+}
+
+impl Dict {
+    /// Proposes at most `max_suggestions` headwords, which are similar to `query_term`.
+    ///
+    /// Only the languages searched in `query_direction` are considered.
+    /// The suggestions are ordered by ascending edit distance.
+    pub fn suggest(&self, query_term: &str, query_direction: QueryDirection, max_suggestions: usize) -> Vec<Suggestion> {
+        let term: Vec<char> = normalize_spelling(query_term.trim()).chars().collect();
+
+        // Allow more typos in longer words
+        let max_distance = ((term.len() + 2) / 4).clamp(1, 3);
+
+        let mut seen = HashSet::new();
+        let mut suggestions = vec![];
+
+        for entry in &self.entries {
+            let words = match query_direction {
+                QueryDirection::ToRight => vec![(&entry.left_word, &self.languages.left_language)],
+                QueryDirection::ToLeft => vec![(&entry.right_word, &self.languages.right_language)],
+                QueryDirection::Bidirectional => vec![
+                    (&entry.left_word, &self.languages.left_language),
+                    (&entry.right_word, &self.languages.right_language),
+                ],
+            };
+
+            for (word, language) in words {
+                let headword = word.plain_word();
+                let normalized: Vec<char> = normalize_spelling(&headword).chars().collect();
+
+                // The length difference is a lower bound of the edit distance
+                if normalized.is_empty() || normalized.len().abs_diff(term.len()) > max_distance {
+                    continue;
+                }
+
+                if !seen.insert((headword.clone(), language)) {
+                    continue;
+                }
+
+                let distance = edit_distance(&term, &normalized);
+
+                if distance <= max_distance {
+                    suggestions.push(Suggestion {
+                        word: headword,
+                        language: language.clone(),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        suggestions.sort_by(|left, right| {
+            left.distance.cmp(&right.distance)
+                .then_with(|| left.word.cmp(&right.word))
+        });
+        suggestions.truncate(max_suggestions);
+
+        suggestions
+    }
+}
+
+/// Levenshtein distance between two words.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous_row: Vec<usize> = (0..b.len() + 1).collect();
+    let mut current_row = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current_row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = if a_char == b_char { 0 } else { 1 };
+
+            current_row[j + 1] = min(
+                previous_row[j] + substitution_cost,
+                min(previous_row[j + 1] + 1, current_row[j] + 1),
+            );
+        }
+
+        ::std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    previous_row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+
+        edit_distance(&a, &b)
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(0, distance("", ""));
+        assert_eq!(3, distance("", "foo"));
+        assert_eq!(3, distance("foo", ""));
+        assert_eq!(0, distance("haus", "haus"));
+        assert_eq!(1, distance("haus", "maus"));
+        assert_eq!(1, distance("haus", "hause"));
+        assert_eq!(3, distance("kitten", "sitting"));
+        assert_eq!(1, distance("wörter", "wörte"));
+    }
+
+    #[test]
+    fn test_normalized_distance() {
+        assert_eq!(0, distance(&normalize_spelling("Straße"), &normalize_spelling("strasse")));
+        assert_eq!(0, distance(&normalize_spelling("Café"), &normalize_spelling("cafe")));
+    }
+}
@@ -0,0 +1,128 @@
+//! Discovery of synonyms through shared translations.
+//!
+//! Two words of the same language, which translate to the same word of the other language,
+//! are candidate synonyms, e.g. `Haus` and `Gebäude` are both translated to `building`.
+
+use super::*;
+
+use std::collections::{HashMap, HashSet};
+
+/// A word of the same language as the query term, which shares translations with it.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Synonym {
+    /// The plain word of the synonym.
+    pub word: String,
+    /// The translations shared with the query term, in lowercase.
+    pub shared_translations: Vec<String>,
+}
+
+impl Synonym {
+    /// The number of translations shared with the query term, used for ranking.
+    pub fn shared_translation_count(&self) -> usize {
+        self.shared_translations.len()
+    }
+}
+
+impl Dict {
+    /// Finds the words of `language`, which share at least one translation with `term`.
+    ///
+    /// The words are compared by their lowercase plain word (see `DictWord::plain_word`).
+    /// Words are only connected through entries of compatible word classes,
+    /// entries without word classes are compatible to all entries.
+    ///
+    /// The synonyms are ordered by descending number of shared translations.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DictError::InvalidSourceLanguage`
+    /// if `language` is not one of the two languages of the `Dict`.
+    pub fn synonyms(&self, term: &str, language: &Language) -> DictResult<Vec<Synonym>> {
+        let query_direction = self.languages.infer_query_direction(language)?;
+
+        let words = |entry: &DictEntry| -> (String, String) {
+            let (word, translation) = match query_direction {
+                QueryDirection::ToLeft => (&entry.right_word, &entry.left_word),
+                _ => (&entry.left_word, &entry.right_word),
+            };
+
+            (word.plain_word(), translation.plain_word().to_lowercase())
+        };
+
+        let term = term.trim().to_lowercase();
+
+        // The word classes of the entries linking the term to each of its translations
+        let mut translations: HashMap<String, Vec<&[WordClass]>> = HashMap::new();
+
+        for entry in &self.entries {
+            let (word, translation) = words(entry);
+
+            if word.to_lowercase() == term {
+                translations.entry(translation).or_default().push(&entry.word_classes);
+            }
+        }
+
+        let mut synonyms: HashMap<String, Synonym> = HashMap::new();
+        let mut links = HashSet::new();
+
+        for entry in &self.entries {
+            let (word, translation) = words(entry);
+            let key = word.to_lowercase();
+
+            if key == term || key.is_empty() {
+                continue;
+            }
+
+            let compatible = match translations.get(&translation) {
+                Some(term_word_classes) => term_word_classes.iter()
+                    .any(|word_classes| word_classes_compatible(word_classes, &entry.word_classes)),
+                None => false,
+            };
+
+            // Count each shared translation once, even if it is linked by several entries
+            if !compatible || !links.insert((key.clone(), translation.clone())) {
+                continue;
+            }
+
+            synonyms.entry(key)
+                .or_insert_with(|| Synonym {
+                    word,
+                    shared_translations: vec![],
+                })
+                .shared_translations.push(translation);
+        }
+
+        let mut synonyms: Vec<Synonym> = synonyms.into_values().collect();
+
+        synonyms.sort_by(|left, right| {
+            right.shared_translation_count().cmp(&left.shared_translation_count())
+                .then_with(|| left.word.cmp(&right.word))
+        });
+
+        Ok(synonyms)
+    }
+}
+
+fn word_classes_compatible(left: &[WordClass], right: &[WordClass]) -> bool {
+    left.is_empty() || right.is_empty() || left.iter().any(|word_class| right.contains(word_class))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synonyms() {
+        let dict = Dict::create("tests/database/test_database_synonyms.txt").unwrap();
+
+        // `Heim` and `Zuhause` are both translated to `home`, the adjective `heimisch` has another word class
+        let synonyms = dict.synonyms("heim", &Language::DE).unwrap();
+        assert_eq!(vec![Synonym { word: "Zuhause".to_string(), shared_translations: vec!["home".to_string()] }], synonyms);
+        assert_eq!("Heim", dict.synonyms("Zuhause", &Language::DE).unwrap()[0].word);
+
+        // `home` is the only English word of both entries
+        assert!(dict.synonyms("home", &Language::EN).unwrap().is_empty());
+        assert!(dict.synonyms("unknown", &Language::DE).unwrap().is_empty());
+
+        assert!(dict.synonyms("heim", &Language::FR).is_err());
+    }
+}
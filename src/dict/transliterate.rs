@@ -0,0 +1,267 @@
+//! Romanization of Cyrillic (Russian, Bulgarian, Serbian) and Greek words.
+//!
+//! Used to match Latin-script queries against words of these languages
+//! and to show a romanized form of the words.
+//!
+//! The tables follow common romanization systems:
+//!
+//! - Russian: BGN/PCGN, without diacritics (`щ` -> `shch`)
+//! - Bulgarian: Streamlined System (`щ` -> `sht`, `ъ` -> `a`)
+//! - Serbian: the official Latin alphabet (`ђ` -> `đ`, `љ` -> `lj`)
+//! - Greek: ELOT 743, simplified (`αυ` -> `av`, `μπ` -> `mp`)
+//!
+//! Characters without transliteration, e.g. Latin letters, are kept.
+
+use dict::Language;
+
+/// Returns the romanized form of `s`, if there is a transliteration table for `language`.
+///
+/// `жена` (SR) -> `žena`, `щастие` (BG) -> `shtastie`, `Φιλοσοφία` (EL) -> `Filosofia`
+pub fn romanize(s: &str, language: &Language) -> Option<String> {
+    let romanize_char: fn(char) -> Option<&'static str> = match *language {
+        Language::RU => russian,
+        Language::BG => bulgarian,
+        Language::SR => serbian,
+        Language::EL => return Some(romanize_greek(s)),
+        _ => return None,
+    };
+
+    let mut romanized = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match to_lowercase(c).and_then(romanize_char) {
+            Some(replacement) => push_with_case(&mut romanized, replacement, c.is_uppercase()),
+            None => romanized.push(c),
+        }
+    }
+
+    Some(romanized)
+}
+
+/// Returns true, if there is a transliteration table for `language`.
+pub fn has_romanization(language: &Language) -> bool {
+    matches!(*language, Language::RU | Language::BG | Language::SR | Language::EL)
+}
+
+fn to_lowercase(c: char) -> Option<char> {
+    c.to_lowercase().next()
+}
+
+/// Capitalizes the first letter of `replacement`, if the original letter was uppercase.
+fn push_with_case(romanized: &mut String, replacement: &str, is_uppercase: bool) {
+    let mut chars = replacement.chars();
+
+    match chars.next() {
+        Some(first) if is_uppercase => {
+            romanized.extend(first.to_uppercase());
+            romanized.push_str(chars.as_str());
+        }
+        _ => romanized.push_str(replacement),
+    }
+}
+
+fn cyrillic_common(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' => "e",
+        'з' => "z",
+        'и' => "i",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        _ => return None,
+    })
+}
+
+fn russian(c: char) -> Option<&'static str> {
+    cyrillic_common(c).or_else(|| Some(match c {
+        'ё' => "e",
+        'ж' => "zh",
+        'й' => "y",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' => "",
+        'ы' => "y",
+        'ь' => "",
+        'э' => "e",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    }))
+}
+
+fn bulgarian(c: char) -> Option<&'static str> {
+    cyrillic_common(c).or_else(|| Some(match c {
+        'ж' => "zh",
+        'й' => "y",
+        'х' => "h",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "sht",
+        'ъ' => "a",
+        'ь' => "y",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    }))
+}
+
+fn serbian(c: char) -> Option<&'static str> {
+    cyrillic_common(c).or_else(|| Some(match c {
+        'ђ' => "đ",
+        'ж' => "ž",
+        'ј' => "j",
+        'љ' => "lj",
+        'њ' => "nj",
+        'ћ' => "ć",
+        'х' => "h",
+        'ц' => "c",
+        'ч' => "č",
+        'џ' => "dž",
+        'ш' => "š",
+        _ => return None,
+    }))
+}
+
+/// Removes the accent and the diaeresis of a lowercase Greek letter.
+///
+/// Returns true as second value, if the letter has a diaeresis, which prevents a diphthong.
+fn greek_base(c: char) -> (char, bool) {
+    match c {
+        'ά' => ('α', false),
+        'έ' => ('ε', false),
+        'ή' => ('η', false),
+        'ί' => ('ι', false),
+        'ό' => ('ο', false),
+        'ύ' => ('υ', false),
+        'ώ' => ('ω', false),
+        'ϊ' | 'ΐ' => ('ι', true),
+        'ϋ' | 'ΰ' => ('υ', true),
+        'ς' => ('σ', false),
+        c => (c, false),
+    }
+}
+
+fn greek(c: char) -> Option<&'static str> {
+    Some(match c {
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' => "i",
+        'θ' => "th",
+        'ι' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' => "o",
+        _ => return None,
+    })
+}
+
+/// Greek digraphs, which are romanized together.
+fn greek_digraph(first: char, second: char) -> Option<&'static str> {
+    Some(match (first, second) {
+        ('ο', 'υ') => "ou",
+        ('α', 'υ') => "av",
+        ('ε', 'υ') => "ev",
+        ('η', 'υ') => "iv",
+        ('γ', 'γ') => "ng",
+        ('γ', 'ξ') => "nx",
+        ('γ', 'χ') => "nch",
+        _ => return None,
+    })
+}
+
+fn romanize_greek(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut romanized = String::with_capacity(s.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let (base, _) = greek_base(to_lowercase(c).unwrap_or(c));
+
+        let digraph = chars.get(i + 1)
+            .map(|&next| greek_base(to_lowercase(next).unwrap_or(next)))
+            .and_then(|(next_base, has_diaeresis)| if has_diaeresis {
+                None
+            } else {
+                greek_digraph(base, next_base)
+            });
+
+        match digraph {
+            Some(replacement) => {
+                push_with_case(&mut romanized, replacement, c.is_uppercase());
+                i += 2;
+            }
+            None => {
+                match greek(base) {
+                    Some(replacement) => push_with_case(&mut romanized, replacement, c.is_uppercase()),
+                    None => romanized.push(c),
+                }
+                i += 1;
+            }
+        }
+    }
+
+    romanized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_romanize() {
+        let data = vec![
+            ("собака", Language::RU, "sobaka"),
+            ("Щука", Language::RU, "Shchuka"),
+            ("объект", Language::RU, "obekt"),
+            ("щастие", Language::BG, "shtastie"),
+            ("ъгъл", Language::BG, "agal"),
+            ("жена", Language::SR, "žena"),
+            ("Љубав", Language::SR, "Ljubav"),
+            ("Φιλοσοφία", Language::EL, "Filosofia"),
+            ("αυτοκίνητο", Language::EL, "avtokinito"),
+            ("ουρανός", Language::EL, "ouranos"),
+            ("άγγελος", Language::EL, "angelos"),
+            ("Αθήνα (πόλη)", Language::EL, "Athina (poli)"),
+        ];
+
+        for (word, language, expected) in data {
+            assert_eq!(Some(expected.to_string()), romanize(word, &language), "word: {:?}", word);
+        }
+
+        assert_eq!(None, romanize("Haus", &Language::DE));
+    }
+}
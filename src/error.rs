@@ -27,11 +27,31 @@ pub enum DictError {
         word_class: String,
         backtrace: Backtrace,
     },
+    #[fail(display = "Unknown subject: {}", subject)]
+    UnknownSubject {
+        subject: String,
+        backtrace: Backtrace,
+    },
+    #[fail(display = "Unknown region: {}", region)]
+    UnknownRegion {
+        region: String,
+        backtrace: Backtrace,
+    },
+    #[fail(display = "Unknown register: {}", register)]
+    UnknownRegister {
+        register: String,
+        backtrace: Backtrace,
+    },
     #[fail(display = "Unknown query type: {}", query_type)]
     UnknownQueryType {
         query_type: String,
         backtrace: Backtrace,
     },
+    #[fail(display = "Unknown query field: {}", query_field)]
+    UnknownQueryField {
+        query_field: String,
+        backtrace: Backtrace,
+    },
     #[fail(display = "Invalid language code: {}", lang)]
     InvalidLanguageCode {
         lang: String,
@@ -77,6 +97,51 @@ pub enum DictError {
 
     #[fail(display = "{}", _0)]
     Regex(#[cause] regex::Error, Backtrace),
+
+    #[fail(display = "Unexpected {:?} at position {} in query {:?}", token, position, query)]
+    QueryUnexpectedToken {
+        query: String,
+        position: usize,
+        token: String,
+        backtrace: Backtrace,
+    },
+
+    #[fail(display = "Unexpected end of query {:?}", query)]
+    QueryUnexpectedEnd {
+        query: String,
+        backtrace: Backtrace,
+    },
+
+    #[fail(display = "Unterminated phrase at position {} in query {:?}", position, query)]
+    QueryUnterminatedPhrase {
+        query: String,
+        position: usize,
+        backtrace: Backtrace,
+    },
+
+    #[fail(display = "Empty phrase at position {} in query {:?}", position, query)]
+    QueryEmptyPhrase {
+        query: String,
+        position: usize,
+        backtrace: Backtrace,
+    },
+
+    #[fail(display = "Unknown field {:?} at position {} in query {:?}", field, position, query)]
+    QueryUnknownField {
+        query: String,
+        position: usize,
+        field: String,
+        backtrace: Backtrace,
+    },
+
+    #[fail(display = "Invalid value {:?} for field {:?} at position {} in query {:?}", value, field, position, query)]
+    QueryInvalidFieldValue {
+        query: String,
+        position: usize,
+        field: String,
+        value: String,
+        backtrace: Backtrace,
+    },
 }
 
 impl From<csv::Error> for DictError {
@@ -26,4 +26,5 @@ mod dict;
 pub mod error;
 
 pub use dict::*;
+pub use parse::query_ast;
 pub use parse::word_ast;
\ No newline at end of file
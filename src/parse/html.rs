@@ -7,6 +7,7 @@ pub struct HtmlDecodedDictEntry {
     pub left_word: String,
     pub right_word: String,
     pub word_classes: String,
+    pub subjects: String,
 }
 
 impl<'a> From<&'a RawDictEntry> for HtmlDecodedDictEntry {
@@ -15,6 +16,7 @@ impl<'a> From<&'a RawDictEntry> for HtmlDecodedDictEntry {
             left_word: html_decode_with_fallback(&raw.left_word),
             right_word: html_decode_with_fallback(&raw.right_word),
             word_classes: raw.word_classes.clone(),
+            subjects: raw.subjects.clone().unwrap_or_default(),
         }
     }
 }
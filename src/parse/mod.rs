@@ -2,4 +2,5 @@
 
 pub(crate) mod html;
 pub(crate) mod raw_csv;
+pub mod query_ast;
 pub mod word_ast;
\ No newline at end of file
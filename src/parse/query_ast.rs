@@ -0,0 +1,534 @@
+//! Parsing and AST of the boolean query language used by `QueryType::Boolean`.
+//!
+//! # Syntax
+//!
+//! ```text
+//! haus -tür class:noun gender:f lang:de
+//! (haus OR heim) NOT "am Haus"
+//! acronym:USA | comment:Kommentar
+//! ```
+//!
+//! - Terms separated by whitespace (or `AND`, `&`) must all match.
+//! - `OR` or `|` matches if any of both sides match. `AND` binds stronger than `OR`.
+//! - `NOT` or a leading `-` negates the following term or group.
+//! - `"quoted phrases"` match multiple consecutive words.
+//! - Parentheses group expressions.
+//! - `class:`, `gender:`, `acronym:` and `comment:` restrict the word class, the gender,
+//!   the acronyms and the comments of an entry.
+//! - `lang:` sets the language the query is written in and applies to the whole query.
+
+use dict::{Gender, Language, WordClass};
+use error::{DictError, DictResult};
+use failure::Backtrace;
+use std::str::FromStr;
+
+/// Node of the query AST.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryNode {
+    /// A single word: `haus`
+    Term(String),
+    /// Multiple consecutive words: `"am Haus"`
+    Phrase(String),
+    /// A word class restriction: `class:noun`
+    Class(WordClass),
+    /// A gender restriction: `gender:f`
+    Gender(Gender),
+    /// An acronym: `acronym:USA`
+    Acronym(String),
+    /// A word in a comment: `comment:Kommentar`
+    Comment(String),
+    /// Negation: `-tür`, `NOT tür`
+    Not(Box<QueryNode>),
+    /// Conjunction: `haus tür`, `haus AND tür`. An empty conjunction matches every entry.
+    And(Vec<QueryNode>),
+    /// Disjunction: `haus OR tür`, `haus | tür`
+    Or(Vec<QueryNode>),
+}
+
+impl QueryNode {
+    /// Returns all terms and phrases, which are not negated.
+    pub fn positive_terms(&self) -> Vec<&str> {
+        use self::QueryNode::*;
+
+        match *self {
+            Term(ref term) | Phrase(ref term) => vec![term.as_str()],
+            And(ref nodes) | Or(ref nodes) => nodes.iter().flat_map(QueryNode::positive_terms).collect(),
+            Class(_) | Gender(_) | Acronym(_) | Comment(_) | Not(_) => vec![],
+        }
+    }
+}
+
+/// A parsed query of the boolean query language.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryExpr {
+    /// The root node of the AST.
+    pub root: QueryNode,
+    /// The language the query is written in, set by `lang:`.
+    pub language: Option<Language>,
+}
+
+impl QueryExpr {
+    /// Parses a query string.
+    ///
+    /// # Errors
+    ///
+    /// Returns one of the `DictError::Query*` variants, containing the position of the error.
+    pub fn parse(query: &str) -> DictResult<Self> {
+        let tokens = tokenize(query)?;
+
+        let mut parser = Parser {
+            query,
+            tokens,
+            index: 0,
+            language: None,
+        };
+
+        let root = parser.parse_or()?;
+
+        if let Some(token) = parser.peek() {
+            return Err(parser.unexpected_token(token));
+        }
+
+        Ok(QueryExpr {
+            root,
+            language: parser.language,
+        })
+    }
+}
+
+impl FromStr for QueryExpr {
+    type Err = DictError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        QueryExpr::parse(s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TokenKind {
+    LeftParen,
+    RightParen,
+    And,
+    Or,
+    Not,
+    Word(String),
+    Phrase(String),
+    /// `name:`, the value is the following token
+    Field(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    /// Character position in the query
+    position: usize,
+}
+
+const FIELDS: &[&str] = &["class", "gender", "lang", "acronym", "comment"];
+
+fn is_word_delimiter(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')' || c == '"' || c == '|'
+}
+
+fn tokenize(query: &str) -> DictResult<Vec<Token>> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    // A `-` negates the following token, if it is at the start of a token
+    let mut at_token_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let position = i;
+
+        let kind = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                at_token_start = true;
+                continue;
+            }
+            '(' => {
+                i += 1;
+                TokenKind::LeftParen
+            }
+            ')' => {
+                i += 1;
+                TokenKind::RightParen
+            }
+            '|' => {
+                i += 1;
+                TokenKind::Or
+            }
+            '&' => {
+                i += 1;
+                TokenKind::And
+            }
+            '-' if at_token_start => {
+                i += 1;
+                tokens.push(Token { kind: TokenKind::Not, position });
+                // The negated token follows immediately
+                continue;
+            }
+            '"' => {
+                let (phrase, end) = read_phrase(query, &chars, i)?;
+                i = end;
+                TokenKind::Phrase(phrase)
+            }
+            _ => {
+                let start = i;
+                while i < chars.len() && !is_word_delimiter(chars[i]) && chars[i] != ':' {
+                    i += 1;
+                }
+
+                let word: String = chars[start..i].iter().collect();
+
+                if i < chars.len() && chars[i] == ':' && !word.is_empty() && word.chars().all(char::is_alphabetic) {
+                    let field = word.to_lowercase();
+
+                    if !FIELDS.contains(&field.as_str()) {
+                        return Err(DictError::QueryUnknownField {
+                            query: query.to_string(),
+                            position,
+                            field: word,
+                            backtrace: Backtrace::new(),
+                        });
+                    }
+
+                    i += 1;
+                    tokens.push(Token { kind: TokenKind::Field(field), position });
+                    // The value follows immediately
+                    at_token_start = false;
+                    continue;
+                }
+
+                // Colons in words, e.g. `3:1`
+                while i < chars.len() && !is_word_delimiter(chars[i]) {
+                    i += 1;
+                }
+
+                let word: String = chars[start..i].iter().collect();
+
+                match word.as_str() {
+                    "AND" => TokenKind::And,
+                    "OR" => TokenKind::Or,
+                    "NOT" => TokenKind::Not,
+                    _ => TokenKind::Word(word),
+                }
+            }
+        };
+
+        tokens.push(Token { kind, position });
+        at_token_start = false;
+    }
+
+    Ok(tokens)
+}
+
+fn read_phrase(query: &str, chars: &[char], start: usize) -> DictResult<(String, usize)> {
+    let mut end = start + 1;
+
+    while end < chars.len() && chars[end] != '"' {
+        end += 1;
+    }
+
+    if end == chars.len() {
+        return Err(DictError::QueryUnterminatedPhrase {
+            query: query.to_string(),
+            position: start,
+            backtrace: Backtrace::new(),
+        });
+    }
+
+    let phrase = chars[start + 1..end].iter().collect::<String>();
+    let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    // An empty phrase would match every word boundary
+    if phrase.is_empty() {
+        return Err(DictError::QueryEmptyPhrase {
+            query: query.to_string(),
+            position: start,
+            backtrace: Backtrace::new(),
+        });
+    }
+
+    Ok((phrase, end + 1))
+}
+
+struct Parser<'a> {
+    query: &'a str,
+    tokens: Vec<Token>,
+    index: usize,
+    language: Option<Language>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.index).cloned()
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek();
+        self.index += 1;
+        token
+    }
+
+    fn unexpected_token(&self, token: Token) -> DictError {
+        let token_string = match token.kind {
+            TokenKind::LeftParen => "(".to_string(),
+            TokenKind::RightParen => ")".to_string(),
+            TokenKind::And => "AND".to_string(),
+            TokenKind::Or => "OR".to_string(),
+            TokenKind::Not => "NOT".to_string(),
+            TokenKind::Word(word) => word,
+            TokenKind::Phrase(phrase) => format!("\"{}\"", phrase),
+            TokenKind::Field(field) => format!("{}:", field),
+        };
+
+        DictError::QueryUnexpectedToken {
+            query: self.query.to_string(),
+            position: token.position,
+            token: token_string,
+            backtrace: Backtrace::new(),
+        }
+    }
+
+    fn unexpected_end(&self) -> DictError {
+        DictError::QueryUnexpectedEnd {
+            query: self.query.to_string(),
+            backtrace: Backtrace::new(),
+        }
+    }
+
+    /// `or := and (("OR" | "|") and)*`
+    fn parse_or(&mut self) -> DictResult<QueryNode> {
+        let mut nodes = vec![self.parse_and()?];
+
+        while let Some(Token { kind: TokenKind::Or, .. }) = self.peek() {
+            self.next();
+            nodes.push(self.parse_and()?);
+        }
+
+        Ok(if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            QueryNode::Or(nodes)
+        })
+    }
+
+    /// `and := unary (["AND" | "&"] unary)*`
+    fn parse_and(&mut self) -> DictResult<QueryNode> {
+        let mut nodes = vec![];
+        // Language fields are not part of the AST, but the conjunction must not be empty
+        let mut parsed_any = false;
+
+        loop {
+            match self.peek().map(|token| token.kind) {
+                None | Some(TokenKind::Or) | Some(TokenKind::RightParen) => break,
+                Some(TokenKind::And) => {
+                    if !parsed_any {
+                        let token = self.peek().unwrap();
+                        return Err(self.unexpected_token(token));
+                    }
+                    self.next();
+
+                    match self.peek() {
+                        None => return Err(self.unexpected_end()),
+                        Some(token) => match token.kind {
+                            TokenKind::And | TokenKind::Or | TokenKind::RightParen => {
+                                return Err(self.unexpected_token(token));
+                            }
+                            _ => continue,
+                        },
+                    }
+                }
+                Some(_) => {}
+            }
+
+            parsed_any = true;
+
+            if let Some(node) = self.parse_unary(false)? {
+                nodes.push(node);
+            }
+        }
+
+        if !parsed_any {
+            return match self.peek() {
+                Some(token) => Err(self.unexpected_token(token)),
+                None => Err(self.unexpected_end()),
+            };
+        }
+
+        Ok(if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            QueryNode::And(nodes)
+        })
+    }
+
+    /// `unary := ("NOT" | "-") unary | primary`
+    ///
+    /// Returns `None` for `lang:` fields.
+    fn parse_unary(&mut self, negated: bool) -> DictResult<Option<QueryNode>> {
+        if let Some(Token { kind: TokenKind::Not, .. }) = self.peek() {
+            self.next();
+
+            return match self.parse_unary(!negated)? {
+                Some(node) => Ok(Some(QueryNode::Not(Box::new(node)))),
+                None => Ok(None),
+            };
+        }
+
+        self.parse_primary(negated)
+    }
+
+    /// `primary := "(" or ")" | field value | word | phrase`
+    fn parse_primary(&mut self, negated: bool) -> DictResult<Option<QueryNode>> {
+        let token = match self.next() {
+            Some(token) => token,
+            None => return Err(self.unexpected_end()),
+        };
+
+        let node = match token.kind {
+            TokenKind::LeftParen => {
+                let node = self.parse_or()?;
+
+                match self.next() {
+                    Some(Token { kind: TokenKind::RightParen, .. }) => node,
+                    Some(token) => return Err(self.unexpected_token(token)),
+                    None => return Err(self.unexpected_end()),
+                }
+            }
+            TokenKind::Word(word) => QueryNode::Term(word),
+            TokenKind::Phrase(phrase) => QueryNode::Phrase(phrase),
+            TokenKind::Field(field) => return self.parse_field(&field, token.position, negated),
+            _ => return Err(self.unexpected_token(token)),
+        };
+
+        Ok(Some(node))
+    }
+
+    fn parse_field(&mut self, field: &str, position: usize, negated: bool) -> DictResult<Option<QueryNode>> {
+        let value = match self.next() {
+            Some(Token { kind: TokenKind::Word(value), .. }) |
+            Some(Token { kind: TokenKind::Phrase(value), .. }) => value,
+            Some(token) => return Err(self.unexpected_token(token)),
+            None => return Err(self.unexpected_end()),
+        };
+
+        let query = self.query;
+
+        let invalid_value = |value: &str| DictError::QueryInvalidFieldValue {
+            query: query.to_string(),
+            position,
+            field: field.to_string(),
+            value: value.to_string(),
+            backtrace: Backtrace::new(),
+        };
+
+        Ok(Some(match field {
+            "class" => QueryNode::Class(value.to_lowercase().parse().map_err(|_| invalid_value(&value))?),
+            "gender" => QueryNode::Gender(value.to_lowercase().parse().map_err(|_| invalid_value(&value))?),
+            "acronym" => QueryNode::Acronym(value),
+            "comment" => QueryNode::Comment(value),
+            "lang" => {
+                let language: Language = value.parse().map_err(|_| invalid_value(&value))?;
+
+                let conflicting = match self.language {
+                    Some(ref previous) => *previous != language,
+                    None => false,
+                };
+
+                if negated || conflicting {
+                    return Err(invalid_value(&value));
+                }
+
+                self.language = Some(language);
+
+                return Ok(None);
+            }
+            _ => unreachable!("Unknown fields are rejected by the tokenizer"),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::QueryNode::*;
+
+    fn term(s: &str) -> QueryNode {
+        Term(s.to_string())
+    }
+
+    #[test]
+    fn test_implicit_and() {
+        let expr = QueryExpr::parse("haus -tür class:noun gender:f lang:de").unwrap();
+
+        assert_eq!(And(vec![
+            term("haus"),
+            Not(Box::new(term("tür"))),
+            Class(WordClass::Noun),
+            Gender(::dict::Gender::Feminine),
+        ]), expr.root);
+        assert_eq!(Some(Language::DE), expr.language);
+    }
+
+    #[test]
+    fn test_precedence() {
+        let expr = QueryExpr::parse("a b OR c | NOT (d e-f)").unwrap();
+
+        assert_eq!(Or(vec![
+            And(vec![term("a"), term("b")]),
+            term("c"),
+            Not(Box::new(And(vec![term("d"), term("e-f")]))),
+        ]), expr.root);
+    }
+
+    #[test]
+    fn test_phrases_and_fields() {
+        let expr = QueryExpr::parse(r#""am  Haus" acronym:USA comment:"fig. use" 3:1"#).unwrap();
+
+        assert_eq!(And(vec![
+            Phrase("am Haus".to_string()),
+            Acronym("USA".to_string()),
+            Comment("fig. use".to_string()),
+            term("3:1"),
+        ]), expr.root);
+    }
+
+    #[test]
+    fn test_only_language() {
+        let expr = QueryExpr::parse("lang:en").unwrap();
+
+        assert_eq!(And(vec![]), expr.root);
+        assert_eq!(Some(Language::EN), expr.language);
+    }
+
+    #[test]
+    fn test_errors() {
+        let position = |query: &str| match QueryExpr::parse(query) {
+            Err(DictError::QueryUnexpectedToken { position, .. }) |
+            Err(DictError::QueryUnterminatedPhrase { position, .. }) |
+            Err(DictError::QueryEmptyPhrase { position, .. }) |
+            Err(DictError::QueryUnknownField { position, .. }) |
+            Err(DictError::QueryInvalidFieldValue { position, .. }) => Some(position),
+            Err(DictError::QueryUnexpectedEnd { .. }) => None,
+            other => panic!("Unexpected result for {:?}: {:?}", query, other),
+        };
+
+        assert_eq!(None, position(""));
+        assert_eq!(None, position("haus OR"));
+        assert_eq!(None, position("(haus"));
+        assert_eq!(Some(5), position("haus )"));
+        assert_eq!(Some(0), position("OR haus"));
+        assert_eq!(Some(5), position("haus \"tür"));
+        assert_eq!(Some(5), position("haus \"\""));
+        assert_eq!(Some(0), position("\"  \" haus"));
+        assert_eq!(Some(6), position("class:\"\""));
+        assert_eq!(Some(5), position("haus farbe:rot"));
+        assert_eq!(Some(0), position("class:foo"));
+        assert_eq!(Some(9), position("lang:de -lang:de"));
+        assert_eq!(Some(8), position("lang:de lang:en"));
+    }
+}
@@ -10,20 +10,35 @@ pub struct RawDictEntry {
     pub left_word: String,
     pub right_word: String,
     pub word_classes: String,
+    /// The subject column, which is missing in older databases
+    #[serde(default)]
+    pub subjects: Option<String>,
 }
 
-pub fn incomplete_records_filter(record: &Result<RawDictEntry, csv::Error>) -> bool {
+/// The number of columns of a complete record: left word, right word and word classes.
+/// The subject column is optional.
+const COMPLETE_RECORD_LEN: usize = 3;
+
+/// The number of columns of a record with the optional subject column.
+const MAX_RECORD_LEN: usize = 4;
+
+pub fn incomplete_records_filter(record: &Result<csv::StringRecord, csv::Error>) -> bool {
+    match *record {
+        Ok(ref record) if record.len() < COMPLETE_RECORD_LEN => {
+            info!("Drop incomplete entry: {:?}", record);
+            false
+        }
+        _ => true,
+    }
+}
+
+pub fn too_many_columns_filter(record: &Result<csv::StringRecord, csv::Error>) -> bool {
     match *record {
-        Ok(_) => true,
-        Err(ref err) => {
-            match *err.kind() {
-                csv::ErrorKind::UnequalLengths { .. } => {
-                    info!("Drop incomplete entry: {:?}", err);
-                    false
-                }
-                _ => true,
-            }
+        Ok(ref record) if record.len() > MAX_RECORD_LEN => {
+            info!("Drop entry with too many columns: {:?}", record);
+            false
         }
+        _ => true,
     }
 }
 
@@ -32,6 +47,8 @@ pub fn get_csv_reader_from_path<P: AsRef<Path>>(path: P) -> DictResult<csv::Read
         .delimiter(b'\t')
         .has_headers(false)
         .quoting(false)
+        // The subject column is optional, records with too many columns are dropped by `too_many_columns_filter`
+        .flexible(true)
         .comment(Some(b'#'))
         .from_path(&path)
         .map_err(|err| DictError::FileOpen {
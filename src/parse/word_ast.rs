@@ -7,10 +7,14 @@ use error::{DictError, DictResult};
 use nom::GetInput;
 use parse::html::HtmlDecodedDictEntry;
 use std::borrow::Borrow;
+use std::cmp::{max, min};
 use std::fmt;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::string::ToString;
 
+/// Maximal number of optional parts of a word, which are expanded by `WordNodes::build_expansions`.
+const MAX_EXPANDED_OPTIONAL_PARTS: usize = 6;
+
 /// Parsing AST node
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum WordNode<T: Borrow<str>> {
@@ -24,6 +28,9 @@ pub enum WordNode<T: Borrow<str>> {
     Square(T),
     /// gender tags
     Curly(T),
+    /// placeholders and reflexive pronouns, e.g. `jdn.`, `sth.`, `sich`.
+    /// Are marked after parsing, see `WordNodes::mark_placeholders`
+    Placeholder(T),
 }
 
 impl<'a, 'b> From<&'a WordNode<&'b str>> for WordNode<String> {
@@ -37,6 +44,7 @@ impl<'a, 'b> From<&'a WordNode<&'b str>> for WordNode<String> {
             Round(s) => Round(s.to_string()),
             Square(s) => Square(s.to_string()),
             Curly(s) => Curly(s.to_string()),
+            Placeholder(s) => Placeholder(s.to_string()),
         }
     }
 }
@@ -47,7 +55,7 @@ impl<'a, T: Borrow<str>> fmt::Display for WordNode<T> {
         use self::WordNode::*;
 
         match *self {
-            Word(ref s) => {
+            Word(ref s) | Placeholder(ref s) => {
                 write!(f, "{}", s.borrow())
             }
             Angle(ref vec_s) => {
@@ -67,28 +75,55 @@ impl<'a, T: Borrow<str>> fmt::Display for WordNode<T> {
 }
 
 impl<'a, T: Borrow<str>> WordNode<T> {
-    fn to_colored_string(&self) -> String {
+    /// Colors a part of the displayed node. Highlighted parts are additionally bold and underlined.
+    fn paint(&self, s: &str, highlighted: bool) -> String {
         use self::WordNode::*;
 
         use colored::Colorize;
 
-        match *self {
-            ref node @ Word(_) => {
-                node.to_string()
-            }
-            ref node @ Angle(_) => {
-                node.to_string().bright_red().to_string()
-            }
-            ref node @ Round(_) => {
-                node.to_string().bright_green().to_string()
-            }
-            ref node @ Square(_) => {
-                node.to_string().bright_blue().to_string()
+        let colored = match *self {
+            Word(_) => s.normal(),
+            Angle(_) => s.bright_red(),
+            Round(_) => s.bright_green(),
+            Square(_) => s.bright_blue(),
+            Curly(_) => s.bright_cyan(),
+            Placeholder(_) => s.bright_magenta(),
+        };
+
+        if highlighted {
+            colored.bold().underline().to_string()
+        } else {
+            colored.to_string()
+        }
+    }
+
+    /// `spans` are sorted byte ranges within the displayed node.
+    fn to_highlighted_string(&self, spans: &[Range<usize>]) -> String {
+        let display = self.to_string();
+
+        let mut highlighted = String::new();
+        let mut position = 0;
+
+        for span in spans {
+            let start = max(span.start, position);
+
+            if start >= span.end {
+                continue;
             }
-            ref node @ Curly(_) => {
-                node.to_string().bright_cyan().to_string()
+
+            if position < start {
+                highlighted.push_str(&self.paint(&display[position..start], false));
             }
+            highlighted.push_str(&self.paint(&display[start..span.end], true));
+
+            position = span.end;
+        }
+
+        if position < display.len() || display.is_empty() {
+            highlighted.push_str(&self.paint(&display[position..], false));
         }
+
+        highlighted
     }
 }
 
@@ -158,21 +193,88 @@ impl<'a> WordNodes<&'a str> {
             })
     }
 
-    fn with_fallback_from(s: &'a str) -> Self {
+    /// Returns true as second value, if the fallback was used.
+    fn with_fallback_from(s: &'a str) -> (Self, bool) {
         match WordNodes::try_from(s) {
-            Ok(node) => node,
+            Ok(node) => (node, false),
             Err(err) => {
                 info!("Using WordNode fallback: {}", err);
 
-                WordNodes {
+                (WordNodes {
                     nodes: vec![WordNode::Word(s)],
-                }
+                }, true)
             }
         }
     }
 }
 
+impl WordNodes<String> {
+    /// Converts the words, for which `is_placeholder` returns true, into `WordNode::Placeholder`.
+    /// `is_placeholder` gets the position of the word among the words and the word itself.
+    ///
+    /// Nothing is converted, if no word would be left, e.g. `etw.`
+    pub(crate) fn mark_placeholders<F>(&mut self, is_placeholder: F) where F: Fn(usize, &str) -> bool {
+        use self::WordNode::*;
+        use std::mem;
+
+        let placeholder_positions: Vec<bool> = self.nodes.iter()
+            .filter_map(|node| match *node {
+                Word(ref s) => Some(s),
+                _ => None,
+            })
+            .enumerate()
+            .map(|(position, word)| is_placeholder(position, word))
+            .collect();
+
+        if placeholder_positions.iter().all(|&is_placeholder| is_placeholder) {
+            return;
+        }
+
+        let mut placeholder_positions = placeholder_positions.into_iter();
+
+        for node in &mut self.nodes {
+            let word = match *node {
+                Word(ref mut s) if placeholder_positions.next() == Some(true) => mem::take(s),
+                _ => continue,
+            };
+
+            *node = Placeholder(word);
+        }
+    }
+}
+
 impl<T: Borrow<str>> WordNodes<T> {
+    /// The placeholders of the word, see `WordNode::Placeholder`.
+    pub(crate) fn build_placeholders(&self) -> Vec<String> {
+        use self::WordNode::*;
+
+        self.nodes.iter()
+            .filter_map(|node| {
+                match *node {
+                    Placeholder(ref s) => Some(s.borrow().to_string()),
+                    _ => None,
+                }
+            }).collect()
+    }
+
+    /// The displayed word with its placeholders replaced by `expansions` in the same order.
+    pub(crate) fn build_expanded_word(&self, expansions: &[String]) -> String {
+        self.displayed_nodes(expansions).join(" ")
+    }
+
+    /// The displayed text of every node, placeholders are replaced by `expansions` in the same order.
+    /// Placeholders without an expansion are kept.
+    fn displayed_nodes(&self, expansions: &[String]) -> Vec<String> {
+        use self::WordNode::*;
+
+        let mut expansions = expansions.iter();
+
+        self.nodes.iter().map(|node| match *node {
+            Placeholder(_) => expansions.next().cloned().unwrap_or_else(|| node.to_string()),
+            _ => node.to_string(),
+        }).collect()
+    }
+
     pub(crate) fn build_comments(&self) -> Vec<String> {
         use self::WordNode::*;
 
@@ -237,6 +339,94 @@ impl<T: Borrow<str>> WordNodes<T> {
         }).collect::<Vec<_>>().join(" ")
     }
 
+    /// The word with its optional parts, but without brackets. In contrast to the indexed word, the case is kept.
+    pub(crate) fn build_unbracketed_word(&self) -> String {
+        use self::WordNode::*;
+
+        self.nodes.iter().filter_map(|node| {
+            match *node {
+                Word(ref s) | Round(ref s) => {
+                    Some(s.borrow())
+                }
+                _ => None,
+            }
+        }).collect::<Vec<_>>().join(" ")
+    }
+
+    /// The word with its optional parts and placeholders, but without brackets. The case is kept.
+    ///
+    /// `jdn. (telefonisch) anrufen` -> `jdn. telefonisch anrufen`
+    pub(crate) fn build_full_word(&self) -> String {
+        use self::WordNode::*;
+
+        self.nodes.iter().filter_map(|node| {
+            match *node {
+                Word(ref s) | Round(ref s) | Placeholder(ref s) => {
+                    Some(s.borrow())
+                }
+                _ => None,
+            }
+        }).collect::<Vec<_>>().join(" ")
+    }
+
+    /// All variants of the word with and without each of its optional parts, without brackets.
+    ///
+    /// `(a) foo (b)` -> `["foo", "a foo", "foo b", "a foo b"]`
+    ///
+    /// Only the first `MAX_EXPANDED_OPTIONAL_PARTS` optional parts are expanded, further optional parts are always included.
+    pub(crate) fn build_expansions(&self) -> Vec<String> {
+        use self::WordNode::*;
+
+        self.expansion_selections().iter().map(|selection| {
+            self.nodes.iter().zip(selection).filter_map(|(node, &selected)| {
+                match *node {
+                    Word(ref s) | Round(ref s) if selected => Some(s.borrow()),
+                    _ => None,
+                }
+            }).collect::<Vec<_>>().join(" ")
+        }).collect()
+    }
+
+    /// For each of the `build_expansions`, whether a node is part of the expansion.
+    pub(crate) fn expansion_selections(&self) -> Vec<Vec<bool>> {
+        use self::WordNode::*;
+
+        let optional_parts = self.nodes.iter().filter(|node| matches!(**node, Round(_))).count();
+
+        let expanded_parts = min(optional_parts, MAX_EXPANDED_OPTIONAL_PARTS);
+
+        (0..1usize << expanded_parts).map(|included_mask| {
+            let mut optional_index = 0;
+
+            self.nodes.iter().map(|node| {
+                match *node {
+                    Word(_) => true,
+                    Round(_) => {
+                        let included = optional_index >= expanded_parts || included_mask & (1 << optional_index) != 0;
+                        optional_index += 1;
+
+                        included
+                    }
+                    _ => false,
+                }
+            }).collect()
+        }).collect()
+    }
+
+    /// The byte offset of every node in the displayed word.
+    pub(crate) fn display_offsets(&self) -> Vec<usize> {
+        let mut offset = 0;
+
+        self.nodes.iter().map(|node| {
+            let node_offset = offset;
+
+            // Nodes are separated by a space
+            offset += node.to_string().len() + 1;
+
+            node_offset
+        }).collect()
+    }
+
     pub(crate) fn build_indexed_word(&self) -> String {
         use self::WordNode::*;
 
@@ -261,11 +451,38 @@ impl<T: Borrow<str>> WordNodes<T> {
         }).count() as u8
     }
 
-    pub(crate) fn to_colored_string(&self) -> String {
-        self.nodes.iter()
-            .map(|word_node| word_node.to_colored_string())
-            .collect::<Vec<_>>()
-            .join(" ")
+    /// Colors the displayed word and highlights the byte ranges `spans` within it.
+    pub(crate) fn to_highlighted_string(&self, spans: &[Range<usize>]) -> String {
+        self.to_highlighted_expanded_string(spans, &[])
+    }
+
+    /// Like `to_highlighted_string`, but the placeholders are replaced by `expansions` in the same order.
+    ///
+    /// `spans` are byte ranges within the displayed word without expansions.
+    pub(crate) fn to_highlighted_expanded_string(&self, spans: &[Range<usize>], expansions: &[String]) -> String {
+        use self::WordNode::*;
+
+        let mut spans = spans.to_vec();
+        spans.sort_by_key(|span| span.start);
+
+        let displayed_nodes = self.displayed_nodes(expansions);
+
+        self.nodes.iter().zip(self.display_offsets()).zip(displayed_nodes).map(|((node, offset), displayed)| {
+            let node_end = offset + node.to_string().len();
+
+            // Placeholders only match as a whole, which also highlights their expansions
+            if let Placeholder(_) = *node {
+                let highlighted = spans.iter().any(|span| span.start < node_end && span.end > offset);
+                return node.paint(&displayed, highlighted);
+            }
+
+            let node_spans: Vec<_> = spans.iter()
+                .filter(|span| span.start < node_end && span.end > offset)
+                .map(|span| span.start.saturating_sub(offset)..min(span.end, node_end) - offset)
+                .collect();
+
+            node.to_highlighted_string(&node_spans)
+        }).collect::<Vec<_>>().join(" ")
     }
 }
 
@@ -278,16 +495,25 @@ pub(crate) struct WordNodesDictEntry<T: Borrow<str>> {
     pub right_word_nodes: WordNodes<T>,
     /// Simple str representation of word classes
     pub word_classes: T,
+    /// Simple str representation of the subjects
+    pub subjects: T,
+    /// Number of words, which could not be parsed and are represented by the fallback
+    pub fallback_words: u8,
 }
 
 impl<'a> From<&'a HtmlDecodedDictEntry> for WordNodesDictEntry<&'a str> {
     /// Perform conversion from HtmlDecodedDictEntry into WordNodesDictEntry<&str>.
     /// If word can't be parsed, a fallback representation of the word is used.
     fn from(entry: &'a HtmlDecodedDictEntry) -> WordNodesDictEntry<&'a str> {
+        let (left_word_nodes, left_fallback) = WordNodes::with_fallback_from(&entry.left_word);
+        let (right_word_nodes, right_fallback) = WordNodes::with_fallback_from(&entry.right_word);
+
         WordNodesDictEntry {
-            left_word_nodes: WordNodes::with_fallback_from(&entry.left_word),
-            right_word_nodes: WordNodes::with_fallback_from(&entry.right_word),
+            left_word_nodes,
+            right_word_nodes,
             word_classes: &entry.word_classes,
+            subjects: &entry.subjects,
+            fallback_words: left_fallback as u8 + right_fallback as u8,
         }
     }
 }
@@ -296,10 +522,15 @@ impl<'a> From<&'a HtmlDecodedDictEntry> for WordNodesDictEntry<String> {
     /// Perform conversion from HtmlDecodedDictEntry into WordNodesDictEntry<String>.
     /// If word can't be parsed, a fallback representation of the word is used.
     fn from(entry: &'a HtmlDecodedDictEntry) -> WordNodesDictEntry<String> {
+        let (left_word_nodes, left_fallback) = WordNodes::with_fallback_from(&entry.left_word);
+        let (right_word_nodes, right_fallback) = WordNodes::with_fallback_from(&entry.right_word);
+
         WordNodesDictEntry {
-            left_word_nodes: WordNodes::from(&WordNodes::with_fallback_from(&entry.left_word)),
-            right_word_nodes: WordNodes::from(&WordNodes::with_fallback_from(&entry.right_word)),
+            left_word_nodes: WordNodes::from(&left_word_nodes),
+            right_word_nodes: WordNodes::from(&right_word_nodes),
             word_classes: entry.word_classes.to_string(),
+            subjects: entry.subjects.to_string(),
+            fallback_words: left_fallback as u8 + right_fallback as u8,
         }
     }
 }
@@ -365,6 +596,32 @@ mod tests {
         assert_eq!("(foo)", WordNode::Round("foo").to_string());
         assert_eq!("[foo]", WordNode::Square("foo").to_string());
         assert_eq!("{foo}", WordNode::Curly("foo").to_string());
+        assert_eq!("jdn.", WordNode::Placeholder("jdn.").to_string());
+    }
+
+    #[test]
+    fn test_mark_placeholders() {
+        let mut nodes = WordNodes::from(&WordNodes::try_from("jdn. (telefonisch) anrufen").unwrap());
+        nodes.mark_placeholders(|_, word| word == "jdn.");
+
+        assert_eq!(WordNode::Placeholder("jdn.".to_string()), nodes[0]);
+        assert_eq!("telefonisch anrufen", nodes.build_indexed_word());
+        assert_eq!(2, nodes.count_words());
+        assert_eq!("jemanden (telefonisch) anrufen", nodes.build_expanded_word(&["jemanden".to_string()]));
+
+        // At least one word is kept
+        let mut nodes = WordNodes::from(&WordNodes::try_from("etw. [Akk.]").unwrap());
+        nodes.mark_placeholders(|_, word| word == "etw.");
+
+        assert_eq!(WordNode::Word("etw.".to_string()), nodes[0]);
+    }
+
+    #[test]
+    fn test_build_expansions() {
+        let nodes = WordNodes::try_from("(a) foo {f} (b)").unwrap();
+
+        assert_eq!(vec!["foo", "a foo", "foo b", "a foo b"], nodes.build_expansions());
+        assert_eq!(vec!["foo"], WordNodes::try_from("foo [bar]").unwrap().build_expansions());
     }
 
     #[test]